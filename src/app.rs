@@ -1,30 +1,55 @@
-use std::{io::Write, process::Stdio, rc::Rc, sync::RwLock};
+use std::{
+    fs::File,
+    io::BufReader,
+    path::Path,
+    process::Stdio,
+    rc::Rc,
+    sync::RwLock,
+    time::{Duration, Instant},
+};
 
 use colored::Colorize;
 
-use syntect::{
-    easy::HighlightLines, highlighting::ThemeSet, parsing::SyntaxSet,
-    util::as_24_bit_terminal_escaped,
+use tabled::settings::Style;
+use terminal_size::{terminal_size, Height, Width};
+use yasqlplus_client::wrapper::{
+    get_connect_info, Column, Connection, DiagInfo, Error, Executed, LazyExecuted, ParamDirection,
+    Parameter, Type, Value,
 };
-use tabled::{
-    settings::{object::Cell as TableCell, Format, Modify, Style},
-    Table,
+
+use crate::command::{
+    self, find_bind_variables, redact_command, ColumnCommand, Command, ErrorAction, ErrorCondition,
+    Expanded, InternalCommand, PagerMode, ParseError, ResultFormat, SessionCommand, SpoolCommand,
+    TimingCommand, Transaction, VariableType,
 };
-use terminal_size::{terminal_size, Height, Width};
-use yasqlplus_client::wrapper::{Connection, DiagInfo, Error, Executed, LazyExecuted};
 
-use crate::command::{self, Command, InternalCommand, ParseError};
+use crate::config::{resolve_connection, ConfigError, Profile};
+use crate::passfile::find_password;
 
 use self::{
-    context::Context,
-    input::{Input, InputError},
-    table::ColumnWrapper,
+    context::{Context, Session, DEFAULT_SESSION},
+    describe::describe,
+    editor::{edit, edit_file},
+    format::{create_formatter, ColumnsFormat, Fitting, FormatOptions, ResultFormatter},
+    input::{BufReaderInput, Input, InputError},
+    metadata::{changes_metadata, Metadata},
+    output::Output,
+    theme::ThemeError,
 };
 
 mod completer;
+mod describe;
+mod editor;
+mod format;
 mod helper;
 mod highlight;
-mod table;
+mod hinter;
+mod keywords;
+mod metadata;
+mod output;
+mod pretty;
+mod theme;
+mod tokenizer;
 mod validate;
 
 pub mod context;
@@ -33,6 +58,13 @@ pub mod input;
 pub struct App {
     context: Rc<RwLock<Context>>,
     input: Box<dyn Input>,
+    output: Output,
+}
+
+/// Rows fetched by a query and time spent.
+struct Fetched {
+    rows: usize,
+    elapsed: Duration,
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -45,28 +77,77 @@ pub enum AppError {
 
     #[error(transparent)]
     Io(#[from] std::io::Error),
+
+    #[error("Bind variable \"{0}\" not declared.")]
+    UndeclaredVariable(String),
+
+    #[error("Exit with code {0}")]
+    Exit(i32),
+
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+
+    #[error(transparent)]
+    Theme(#[from] ThemeError),
 }
 
 impl App {
     pub fn new(input: Box<dyn Input>, context: Rc<RwLock<Context>>) -> Result<Self, AppError> {
-        Ok(App { input, context })
+        Ok(App {
+            input,
+            context,
+            output: Output::default(),
+        })
+    }
+
+    /// Runs until no more commands, returns the exit code.
+    pub fn run(&mut self) -> Result<i32, AppError> {
+        Ok(self.run_until_exit()?.unwrap_or(0))
+    }
+
+    /// Runs commands in the script as if they are typed in, e.g. `login.sql`.
+    ///
+    /// Returns the exit code if exiting by `WHENEVER`.
+    pub fn run_script(&mut self, path: &Path) -> Result<Option<i32>, AppError> {
+        let script = BufReaderInput::new(BufReader::new(File::open(path)?));
+        let input = std::mem::replace(&mut self.input, Box::new(script));
+        let result = self.run_until_exit();
+        self.input = input;
+        result
     }
 
-    pub fn run(&mut self) -> Result<(), AppError> {
+    /// Runs until no more commands, returns the exit code if exiting by `WHENEVER`.
+    fn run_until_exit(&mut self) -> Result<Option<i32>, AppError> {
         loop {
             if let Err(err) = self.step(None) {
                 match err {
                     AppError::Input(input_error) => match input_error {
                         InputError::Eof => break,
                         InputError::Cancelled => {}
-                        _ => println!("{input_error}"),
+                        InputError::Parse(_) => {
+                            writeln!(self.output, "{input_error}");
+                            if let Some(code) = self.on_client_error()? {
+                                return Ok(Some(code));
+                            }
+                        }
+                        _ => writeln!(self.output, "{input_error}"),
                     },
-                    AppError::Io(err) => println!("{err}"),
-                    AppError::Client(err) => self.print_execute_sql_error(err)?,
+                    AppError::Io(err) => writeln!(self.output, "{err}"),
+                    AppError::UndeclaredVariable(_) => {
+                        writeln!(self.output, "{err}");
+                        if let Some(code) = self.on_client_error()? {
+                            return Ok(Some(code));
+                        }
+                    }
+                    AppError::Client(err) => {
+                        self.print_execute_sql_error(&self.context.read().unwrap(), err)?
+                    }
+                    AppError::Config(_) | AppError::Theme(_) => writeln!(self.output, "{err}"),
+                    AppError::Exit(code) => return Ok(Some(code)),
                 }
             }
         }
-        Ok(())
+        Ok(None)
     }
 
     pub fn step(&mut self, command: Option<(Command, String)>) -> Result<(), AppError> {
@@ -83,18 +164,31 @@ impl App {
             None => (None, None),
         };
         let mut ctx = self.context.write().unwrap();
-        ctx.set_command(command);
 
+        let echo = format!(
+            "{}{}\n",
+            ctx.get_prompt(),
+            redact_command(&command_str.unwrap_or_default())
+        );
         if ctx.need_echo() {
-            println!("{}{}", ctx.get_prompt(), command_str.unwrap_or_default());
+            write!(self.output, "{echo}");
+        } else if command.is_some() {
+            // keep commands in transcript
+            self.output.write_spool(&echo);
         }
 
-        let command = ctx.get_command();
-        if command.is_none() {
-            return Ok(());
+        let result = match &command {
+            Some(command) => self.execute_command(&mut ctx, command),
+            None => Ok(()),
+        };
+        // kept like the buffer of sqlplus
+        if matches!(command, Some(Command::SQL(_))) {
+            ctx.set_command(command);
         }
+        result
+    }
 
-        let command = command.as_ref().unwrap();
+    fn execute_command(&self, ctx: &mut Context, command: &Command) -> Result<(), AppError> {
         if command.need_connection() && ctx.get_connection().is_none() {
             match command {
                 Command::SQL(sql)
@@ -103,7 +197,7 @@ impl App {
                         .nth(0)
                         .map(|l| l.starts_with("--"))
                         .unwrap_or(false)) => {} // single line comment
-                _ => println!("Not connected!"),
+                _ => writeln!(self.output, "Not connected!"),
             }
 
             return Ok(());
@@ -111,39 +205,251 @@ impl App {
 
         match command {
             Command::Internal(internal) => match internal {
-                InternalCommand::Connect(command::Connection {
-                    host,
-                    port,
-                    username,
-                    password,
-                }) => {
-                    match self.connect(host.clone(), *port, username.clone(), password.clone()) {
-                        Ok((conn, prompt)) => {
-                            ctx.set_connection(Some(conn));
-                            ctx.set_prompt(prompt);
-                            println!("Connected!");
+                InternalCommand::Connect(name, connection) => {
+                    let name = name
+                        .clone()
+                        .or_else(|| ctx.session_name().map(str::to_string))
+                        .unwrap_or_else(|| DEFAULT_SESSION.to_string());
+                    let (
+                        command::Connection {
+                            host,
+                            port,
+                            username,
+                            password,
+                        },
+                        profile,
+                    ) = resolve_connection(connection, ctx.profiles(), &get_connect_info().into());
+                    match self.connect(host, port, username, password, profile) {
+                        Ok(session) => {
+                            ctx.add_session(name, session);
+                            writeln!(self.output, "Connected!");
+                            self.init_session(ctx)
                         }
+                        // open sessions, even one of the same name, are kept as they are
+                        Err(err) => self.connect_failed(ctx, err),
+                    }
+                }
+                InternalCommand::Disconnect => {
+                    if ctx.disconnect() {
+                        writeln!(self.output, "Disconnected");
+                    } else {
+                        writeln!(self.output, "Not connected");
+                    }
+                    Ok(())
+                }
+                InternalCommand::Reconnect => self.reconnect(ctx),
+                InternalCommand::Refresh => {
+                    if let Some(session) = ctx.session() {
+                        session.refresh_metadata();
+                    }
+                    writeln!(self.output, "Refreshing metadata in the background");
+                    Ok(())
+                }
+                InternalCommand::Describe(object) => {
+                    match describe(ctx.get_connection().unwrap(), object) {
+                        Ok(Some(description)) => {
+                            let content = description.to_string();
+                            self.page_if_necessary(ctx, &content);
+                            writeln!(self.output, "{content}");
+                            self.output.end_pager();
+                        }
+                        Ok(None) => writeln!(self.output, "Object {object} does not exist."),
                         Err(err) => {
-                            ctx.set_connection(None);
-                            println!("Failed to connect: ");
-                            self.print_execute_sql_error(err)?;
+                            let (code, lost) = (sql_error_code(&err), err.is_connection_lost());
+                            self.print_execute_sql_error(ctx, err)?;
+                            return self.on_sql_error(ctx, code, lost);
                         }
                     }
                     Ok(())
                 }
-                InternalCommand::Describe(table_or_view) => self.execute_sql_and_show(
-                    ctx.get_connection().as_ref().unwrap(),
-                    &format!("select * from {table_or_view} where 1=2"),
-                    Some(internal),
-                    ctx.less_enabled(),
-                ),
                 InternalCommand::Exit => Err(AppError::Input(InputError::Eof)),
+                InternalCommand::Edit(Some(file)) => {
+                    // `.sql` by default as sqlplus
+                    let path = match Path::new(file).extension() {
+                        Some(_) => file.clone(),
+                        None => format!("{file}.sql"),
+                    };
+                    edit_file(&path)?;
+                    Ok(())
+                }
+                InternalCommand::Edit(None) => {
+                    let sql = match ctx.get_command() {
+                        Some(Command::SQL(sql)) => sql.as_str(),
+                        _ => "",
+                    };
+                    let edited = edit(sql)?;
+                    self.input.prefill(edited.trim_end());
+                    Ok(())
+                }
+                InternalCommand::Variable(name) => {
+                    let variables = match name {
+                        Some(name) => vec![ctx
+                            .get_variable(name)
+                            .ok_or_else(|| AppError::UndeclaredVariable(name.clone()))?],
+                        None => ctx.get_variables().values().collect(),
+                    };
+                    if !variables.is_empty() {
+                        let columns = [text_column("Name"), text_column("Type")];
+                        let rows = variables.iter().map(|variable| {
+                            vec![
+                                Some(Value::VarChar(variable.name.clone())),
+                                Some(Value::VarChar(variable.type_.to_string())),
+                            ]
+                        });
+                        self.show_values(ctx, &columns, rows);
+                    }
+                    Ok(())
+                }
+                InternalCommand::DefineVariable(variable) => {
+                    ctx.set_variable(variable.clone());
+                    Ok(())
+                }
+                InternalCommand::Print(name) => {
+                    let variables = match name {
+                        Some(name) => vec![ctx
+                            .get_variable(name)
+                            .ok_or_else(|| AppError::UndeclaredVariable(name.clone()))?],
+                        None => ctx.get_variables().values().collect(),
+                    };
+                    if !variables.is_empty() {
+                        let columns = variables
+                            .iter()
+                            .map(|x| Column {
+                                type_: match x.type_ {
+                                    VariableType::Number => Type::Number,
+                                    _ => Type::VarChar,
+                                },
+                                ..text_column(&x.name)
+                            })
+                            .collect::<Vec<_>>();
+                        let row = variables
+                            .iter()
+                            .map(|x| {
+                                let value = x.value.clone()?;
+                                Some(match x.type_ {
+                                    VariableType::Number => Value::Number(value),
+                                    _ => Value::VarChar(value),
+                                })
+                            })
+                            .collect();
+                        self.show_values(ctx, &columns, [row]);
+                    }
+                    Ok(())
+                }
+                InternalCommand::Execute(statement) => {
+                    let expanded = ctx.take_expanded();
+                    self.execute_sql_and_show(ctx, &format!("begin\n{statement};\nend;"), expanded)
+                }
+                InternalCommand::Set(setting) => {
+                    if let Err(err) = ctx.apply_setting(setting) {
+                        writeln!(self.output, "{err}");
+                    }
+                    Ok(())
+                }
+                InternalCommand::Column(column) => {
+                    match column {
+                        ColumnCommand::List(None) => {
+                            for (name, settings) in ctx.columns() {
+                                writeln!(self.output, "COLUMN   {name} ON\n{settings}");
+                            }
+                        }
+                        ColumnCommand::List(Some(name)) => match ctx.columns().get(name) {
+                            Some(settings) => {
+                                writeln!(self.output, "COLUMN   {name} ON\n{settings}")
+                            }
+                            None => writeln!(self.output, "COLUMN '{name}' not defined"),
+                        },
+                        ColumnCommand::Clear(name) => {
+                            if !ctx.clear_column(name) {
+                                writeln!(self.output, "COLUMN '{name}' not defined");
+                            }
+                        }
+                        ColumnCommand::Define(name, options) => ctx.define_column(name, options),
+                    }
+                    Ok(())
+                }
+                InternalCommand::Timing(timing) => {
+                    match timing {
+                        TimingCommand::Status => match ctx.timers().len() {
+                            0 => writeln!(self.output, "No timing elements in use"),
+                            n => writeln!(self.output, "{n} timing element(s) in use"),
+                        },
+                        TimingCommand::Start(name) => ctx.start_timer(name.clone()),
+                        TimingCommand::Show(name) => {
+                            self.show_timer(ctx.get_timer(name.as_deref()), name.as_deref())
+                        }
+                        TimingCommand::Stop(name) => self
+                            .show_timer(ctx.stop_timer(name.as_deref()).as_ref(), name.as_deref()),
+                    }
+                    Ok(())
+                }
+                InternalCommand::Session(session) => {
+                    match session {
+                        SessionCommand::List => {
+                            if ctx.sessions().is_empty() {
+                                writeln!(self.output, "No sessions");
+                                return Ok(());
+                            }
+                            let mut builder = tabled::builder::Builder::default();
+                            builder.push_record(["", "Name", "Username", "Host", "Port"]);
+                            for (name, session) in ctx.sessions() {
+                                let current = ctx.session_name() == Some(name.as_str());
+                                builder.push_record([
+                                    if current { "*" } else { "" }.to_string(),
+                                    name.clone(),
+                                    session.username.clone(),
+                                    session.host.clone(),
+                                    session.port.to_string(),
+                                ]);
+                            }
+                            writeln!(self.output, "{}", builder.build().with(Style::rounded()));
+                        }
+                        SessionCommand::Use(name) => {
+                            if !ctx.use_session(name) {
+                                writeln!(self.output, "No session named {name}");
+                            }
+                        }
+                        SessionCommand::Close(name) => {
+                            if ctx.close_session(name) {
+                                writeln!(self.output, "Disconnected {name}");
+                            } else {
+                                writeln!(self.output, "No session named {name}");
+                            }
+                        }
+                    }
+                    Ok(())
+                }
+                InternalCommand::Whenever(condition, action) => {
+                    ctx.set_error_action(*condition, *action);
+                    Ok(())
+                }
+                InternalCommand::Spool(spool) => {
+                    match spool {
+                        SpoolCommand::Status => match self.output.spooling() {
+                            Some(path) => writeln!(self.output, "Currently spooling to {path}"),
+                            None => writeln!(self.output, "Not spooling currently"),
+                        },
+                        SpoolCommand::Off => {
+                            if self.output.spool_off().is_none() {
+                                writeln!(self.output, "Not spooling currently");
+                            }
+                        }
+                        SpoolCommand::To(path, mode) => self.output.spool(path, *mode)?,
+                    }
+                    Ok(())
+                }
             },
             Command::SQL(sql) => {
                 if sql.lines().count() == 1 && sql.starts_with("--") {
                     // comment
                     return Ok(());
                 }
+                let expanded = ctx.take_expanded();
+                let (sql, expanded) = match sql.trim_end().strip_suffix("\\G") {
+                    // like `\G` in mysql
+                    Some(sql) => (sql.trim_end(), Expanded::On),
+                    None => (sql.as_str(), expanded),
+                };
                 let sql = if sql.is_empty() || !sql.ends_with([';', '/']) {
                     sql
                 } else {
@@ -151,28 +457,39 @@ impl App {
                     //               '/' for block
                     &sql[..sql.len() - 1]
                 };
-                self.execute_sql_and_show(
-                    ctx.get_connection().as_ref().unwrap(),
-                    sql,
-                    None,
-                    ctx.less_enabled(),
-                )
+                let result = self.execute_sql_and_show(ctx, sql, expanded);
+                if changes_metadata(sql) {
+                    if let Some(session) = ctx.session() {
+                        session.refresh_metadata();
+                    }
+                }
+                result
             }
             Command::Shell(shell) => {
-                std::process::Command::new("sh")
+                let code = match std::process::Command::new("sh")
                     .arg("-c")
                     .arg(shell)
                     .stdin(Stdio::inherit())
                     .stdout(Stdio::inherit())
                     .stderr(Stdio::inherit())
-                    .spawn()?
-                    .wait()?;
-                Ok(())
+                    .spawn()
+                    .and_then(|mut child| child.wait())
+                {
+                    Ok(status) if status.success() => return Ok(()),
+                    Ok(status) => status.code().unwrap_or(1),
+                    Err(err) => {
+                        writeln!(self.output, "{err}");
+                        err.raw_os_error().unwrap_or(1)
+                    }
+                };
+                self.on_error(ctx, ErrorCondition::OsError, code)
             }
         }
     }
 
-    fn print_execute_sql_error(&self, err: Error) -> Result<(), AppError> {
+    fn print_execute_sql_error(&self, ctx: &Context, err: Error) -> Result<(), AppError> {
+        let theme = ctx.theme();
+        let palette = theme.palette();
         match err {
             Error::YasClient(err) => match err.pos {
                 (0, 0) => {
@@ -182,15 +499,17 @@ impl App {
                         code,
                         ..
                     } = err;
-                    println!(
+                    writeln!(
+                        self.output,
                         "{}",
-                        format!("YAS-{code:0>5}: {message} (SQL State: {sql_state})").red()
+                        format!("YAS-{code:0>5}: {message} (SQL State: {sql_state})")
+                            .color(palette.error)
                     )
                 }
                 (line, column) => match &err.sql {
                     Some(sql) => {
                         if sql.is_empty() {
-                            println!("{}", err.message.red());
+                            writeln!(self.output, "{}", err.message.color(palette.error));
                             return Ok(());
                         }
                         let mut lines = vec![];
@@ -198,24 +517,11 @@ impl App {
                         let heading = format!("  {line} | ");
                         lines.push(format!(
                             "{heading}{code}",
-                            heading = heading.blue(),
-                            code = {
-                                let ps = SyntaxSet::load_defaults_newlines();
-                                let ts = ThemeSet::load_defaults();
-
-                                let syntax = ps.find_syntax_by_extension("sql").unwrap();
-                                let mut h =
-                                    HighlightLines::new(syntax, &ts.themes["base16-ocean.dark"]);
-                                let ranges: Vec<(syntect::highlighting::Style, &str)> = h
-                                    .highlight_line(
-                                        sql.lines().nth(line as usize - 1).unwrap(),
-                                        &ps,
-                                    )
-                                    .unwrap();
-                                let mut escaped = as_24_bit_terminal_escaped(&ranges[..], false);
-                                escaped.push_str("\x1b[0m");
-                                escaped
-                            }
+                            heading = heading.color(palette.heading),
+                            code = theme.highlight(
+                                sql.lines().nth(line as usize - 1).unwrap_or_default(),
+                                false
+                            )
                         ));
                         lines.push(
                             format!(
@@ -223,15 +529,18 @@ impl App {
                                 indent = " ".repeat(heading.len() + column as usize - 1),
                                 message = err.message
                             )
-                            .red()
+                            .color(palette.error)
                             .to_string(),
                         );
-                        println!("{}", lines.join("\n"))
+                        writeln!(self.output, "{}", lines.join("\n"))
                     }
-                    None => println!("{:?}", err),
+                    None => writeln!(self.output, "{:?}", err),
                 },
             },
             Error::Other => todo!(),
+            Error::Unexpected(_) => {
+                writeln!(self.output, "{}", err.to_string().color(palette.error))
+            }
         }
         Ok(())
     }
@@ -239,83 +548,177 @@ impl App {
     fn show_result(
         &self,
         result: LazyExecuted,
-        command: Option<&InternalCommand>,
-        less_enabled: bool,
-    ) -> Result<(), AppError> {
+        expanded: Expanded,
+        ctx: &Context,
+    ) -> Result<Option<Fetched>, AppError> {
+        // time fetching rows, not writing them which could wait for the pager
+        let started = Instant::now();
         let resolved = result.resolve()?;
+        let mut elapsed = started.elapsed();
         match resolved {
             Executed::DQL(result) => {
                 let columns = result.iter_columns().collect::<Vec<_>>();
-                let (mut table, styling, rows) =
-                    if matches!(command, Some(InternalCommand::Describe(_))) {
-                        let styling: Box<dyn FnOnce(&mut Table)> = Box::new(|_: &mut Table| {});
-                        (Table::new(columns.iter().map(ColumnWrapper)), styling, None)
-                    } else {
-                        let mut builder = tabled::builder::Builder::default();
-                        let mut nulls = Vec::<(usize, usize)>::new();
-                        let rows = result.rows().collect::<Vec<_>>();
-                        rows.iter().enumerate().for_each(|(y, row)| {
-                            builder.push_record(row.iter().enumerate().map(
-                                |(x, value)| match value {
-                                    Some(x) => format!("{x}"),
-                                    None => {
-                                        nulls.push((y, x));
-                                        "<null>".to_owned()
-                                    }
-                                },
-                            ));
-                        });
-                        builder.insert_record(0, columns.iter().map(|x| x.name.clone()));
-
-                        let styling: Box<dyn FnOnce(&mut Table)> = Box::new(|table: &mut Table| {
-                            for (row, col) in nulls {
-                                let _ =
-                                    &table.with(Modify::new(TableCell::new(row + 1, col)).with(
-                                        Format::content(|x| x.to_owned().italic().to_string()),
-                                    ));
-                            }
-                        });
-                        (builder.build(), styling, Some(rows.len()))
-                    };
 
-                if rows.is_none() || matches!(rows, Some(row) if row > 0) {
-                    let table = table.with(Style::rounded());
-                    if less_enabled {
-                        self.show_long_if_necessary(&table.to_string());
+                // keep machine readable formats clean
+                let human_readable = matches!(
+                    ctx.result_format(),
+                    ResultFormat::Table | ResultFormat::Text
+                );
+                let mut formatter = self.formatter(ctx, expanded);
+                let mut paging_decided = false;
+                let mut emit = |content: String| {
+                    if content.is_empty() {
+                        return;
+                    }
+                    if !paging_decided {
+                        // decided by the first output which includes heading
+                        self.page_if_necessary(ctx, &content);
+                        paging_decided = true;
+                    }
+                    write!(self.output, "{content}");
+                };
+
+                let columns_format = ColumnsFormat::new(&columns, ctx.columns());
+                emit(formatter.header(&columns_format.columns(&columns)));
+                let mut rows = result.rows();
+                loop {
+                    let started = Instant::now();
+                    let row = rows.next();
+                    elapsed += started.elapsed();
+                    let Some(row) = row else {
+                        break;
+                    };
+                    emit(formatter.row(&columns_format.row(row)));
+                    if self.output.pager_closed() {
+                        break;
                     }
-                    styling(table);
-                    println!("{table}");
                 }
+                emit(formatter.footer());
+                self.output.end_pager();
 
-                if let Some(rows) = rows {
-                    println!("{rows} row(s) fetched");
+                if human_readable {
+                    writeln!(self.output, "{} row(s) fetched", rows.fetched());
                 }
+                return Ok(Some(Fetched {
+                    rows: rows.fetched(),
+                    elapsed,
+                }));
+            }
+            Executed::DML(affection) => {
+                writeln!(self.output, "{} row(s) affected", affection.affected())
             }
-            Executed::DML(affection) => println!("{} row(s) affected", affection.affected()),
-            Executed::DCL(_instruction) => println!("DCL executed"),
-            Executed::Unknown(_) => println!("Succeed"),
+            Executed::DCL(_instruction) => writeln!(self.output, "DCL executed"),
+            Executed::Unknown(_) => writeln!(self.output, "Succeed"),
         };
-        Ok(())
+        Ok(None)
+    }
+
+    /// Creates a formatter of the current `SET MARKUP`.
+    fn formatter(&self, ctx: &Context, expanded: Expanded) -> Box<dyn ResultFormatter> {
+        // lines as wide as the terminal are paged, see `page_if_necessary`
+        let width = terminal_size()
+            .filter(|_| console::Term::stdout().is_term())
+            .map(|(Width(w), _)| (w as usize).saturating_sub(1));
+        create_formatter(FormatOptions {
+            format: ctx.result_format(),
+            pagesize: ctx.pagesize(),
+            expanded,
+            fitting: Fitting {
+                width,
+                max_column_width: ctx.max_column_width(),
+                truncate: ctx.truncate(),
+            },
+            palette: ctx.theme().palette(),
+        })
+    }
+
+    /// Shows bind variables in the same format as query results.
+    fn show_values(
+        &self,
+        ctx: &Context,
+        columns: &[Column],
+        rows: impl IntoIterator<Item = Vec<Option<Value>>>,
+    ) {
+        let mut formatter = self.formatter(ctx, ctx.expanded());
+        let mut content = formatter.header(columns);
+        for row in rows {
+            content.push_str(&formatter.row(&row));
+        }
+        content.push_str(&formatter.footer());
+        self.page_if_necessary(ctx, &content);
+        write!(self.output, "{content}");
+        self.output.end_pager();
+    }
+
+    /// Shows elapsed time of a statement, with fetch statistics if it's a query.
+    fn show_timing(&self, executed: Duration, fetched: Option<Fetched>) {
+        match fetched {
+            Some(Fetched { rows, elapsed }) => {
+                let speed = match elapsed.as_secs_f64() {
+                    secs if secs > 0.0 => format!(", {:.0} rows/s", rows as f64 / secs),
+                    _ => String::new(),
+                };
+                writeln!(
+                    self.output,
+                    "Elapsed: {} (execute: {}, fetch: {}, {rows} row(s){speed})",
+                    format_elapsed(executed + elapsed),
+                    format_elapsed(executed),
+                    format_elapsed(elapsed),
+                )
+            }
+            None => writeln!(self.output, "Elapsed: {}", format_elapsed(executed)),
+        }
+    }
+
+    /// Shows elapsed time of the timer started by `TIMING START`.
+    fn show_timer(&self, timer: Option<&(Option<String>, Instant)>, name: Option<&str>) {
+        match (timer, name) {
+            (Some((name, started)), _) => {
+                if let Some(name) = name {
+                    writeln!(self.output, "Timing for: {name}");
+                }
+                writeln!(
+                    self.output,
+                    "Elapsed: {}",
+                    format_elapsed(started.elapsed())
+                );
+            }
+            (None, Some(name)) => writeln!(self.output, "Timer {name} not found"),
+            (None, None) => writeln!(self.output, "No timing elements in use"),
+        }
     }
 
-    fn show_long_if_necessary(&self, content: &str) {
+    /// Sends following output to the pager if `content` is too wide or high, or always.
+    fn page_if_necessary(&self, ctx: &Context, content: &str) {
         if !console::Term::stdout().is_term() {
             return;
         }
-        let size = terminal_size();
-        if let Some((Width(w), Height(_h))) = size {
-            if console::measure_text_width(content.lines().nth(0).unwrap_or_default()) >= w as _ {
-                if let Ok(mut command) = std::process::Command::new("less")
-                    .arg("-S")
-                    .stdin(Stdio::piped())
-                    .spawn()
-                {
-                    if let Some(mut stdin) = command.stdin.take() {
-                        let _ = stdin.write_all(content.as_bytes());
-                    }
-                    let _ = command.wait();
+        let necessary = match ctx.pager_mode() {
+            PagerMode::Always => true,
+            PagerMode::Never => false,
+            PagerMode::Auto => match terminal_size() {
+                Some((Width(w), Height(h))) => {
+                    console::measure_text_width(content.lines().nth(0).unwrap_or_default())
+                        >= w as _
+                        || content.lines().count() >= h as _
                 }
-            }
+                None => false,
+            },
+        };
+        if !necessary {
+            return;
+        }
+
+        let pager = ctx.pager();
+        let mut args = pager.split_whitespace();
+        let mut command = std::process::Command::new(args.next().unwrap_or("less"));
+        command.args(args);
+        if std::env::var_os("LESS").is_none() {
+            // colors, and long lines chopped
+            command.env("LESS", "RS");
+        }
+        if let Err(err) = self.output.start_pager(command) {
+            writeln!(self.output, "Failed to start pager `{pager}`: {err}");
         }
     }
 
@@ -325,7 +728,8 @@ impl App {
         port: Option<u16>,
         username: Option<String>,
         password: Option<String>,
-    ) -> anyhow::Result<(Connection, String), Error> {
+        profile: Option<Profile>,
+    ) -> anyhow::Result<Session, Error> {
         let host = match host {
             Some(v) => v,
             None => "127.0.0.1".to_owned(),
@@ -333,39 +737,308 @@ impl App {
         let port = port.unwrap_or(1688);
         let username = match username {
             Some(v) => v.clone(),
-            None => self.input.line("Username: ").unwrap_or_default(),
+            None => self
+                .input
+                .line("Username: ")
+                .unwrap_or_default()
+                .trim_end_matches(['\r', '\n'])
+                .to_owned(),
         };
         let password = match password {
             Some(v) => v.clone(),
-            None => self.input.line("Password: ").unwrap_or_default(),
+            None => match find_password(&host, port, &username) {
+                Ok(Some(password)) => password,
+                Ok(None) => self.input.password("Password: ").unwrap_or_default(),
+                Err(err) => {
+                    writeln!(self.output, "{err}");
+                    self.input.password("Password: ").unwrap_or_default()
+                }
+            },
         };
-        match Connection::connect(&host, port, &username, &password) {
-            Ok(conn) => Ok((conn, format!("{username}@{host}:{port} > "))),
-            Err(err) => Err(err),
+        let session = Session {
+            connection: Some(Connection::connect(&host, port, &username, &password)?),
+            username,
+            host,
+            port,
+            password,
+            profile,
+            metadata: Metadata::default(),
+        };
+        session.refresh_metadata();
+        Ok(session)
+    }
+
+    /// Connects the current session again, even if it is disconnected.
+    fn reconnect(&self, ctx: &mut Context) -> Result<(), AppError> {
+        let (Some(name), Some(session)) = (ctx.session_name(), ctx.session()) else {
+            writeln!(self.output, "No connection to reconnect");
+            return Ok(());
+        };
+        let name = name.to_string();
+        let Session {
+            username,
+            host,
+            port,
+            password,
+            profile,
+            ..
+        } = session;
+        let (host, port, username, password, profile) = (
+            host.clone(),
+            *port,
+            username.clone(),
+            password.clone(),
+            profile.clone(),
+        );
+        ctx.disconnect();
+        match self.connect(
+            Some(host),
+            Some(port),
+            Some(username),
+            Some(password),
+            profile,
+        ) {
+            Ok(session) => {
+                ctx.add_session(name, session);
+                writeln!(self.output, "Reconnected!");
+                self.init_session(ctx)
+            }
+            Err(err) => self.connect_failed(ctx, err),
+        }
+    }
+
+    /// Runs initial statements of the profile in the current session.
+    fn init_session(&self, ctx: &mut Context) -> Result<(), AppError> {
+        let statements = ctx
+            .session()
+            .and_then(|x| x.profile.as_ref())
+            .map(|x| x.init_statements())
+            .unwrap_or_default();
+        for statement in statements {
+            match self.execute_sql(ctx, &statement) {
+                Ok(_) => {}
+                Err(AppError::Client(err)) => self.print_execute_sql_error(ctx, err)?,
+                Err(err) => return Err(err),
+            }
         }
+        Ok(())
     }
 
-    fn execute_sql(&self, connection: &Connection, sql: &str) -> Result<LazyExecuted, Error> {
-        let statement = connection.create_statement()?;
-        let result = statement.execute_sql(sql)?;
+    fn connect_failed(&self, ctx: &Context, err: Error) -> Result<(), AppError> {
+        writeln!(self.output, "Failed to connect: ");
+        let code = sql_error_code(&err);
+        self.print_execute_sql_error(ctx, err)?;
+        self.on_error(ctx, ErrorCondition::SqlError, code)
+    }
+
+    fn execute_sql(&self, ctx: &mut Context, sql: &str) -> Result<LazyExecuted, AppError> {
+        let connection = ctx.get_connection().unwrap();
+        let variables = find_bind_variables(sql);
+        if !variables.iter().any(|x| ctx.get_variable(x).is_some()) {
+            // not ours to bind, leave them to the server
+            let statement = connection.create_statement()?;
+            return Ok(statement.execute_sql(sql)?);
+        }
+
+        let mut statement = connection.create_statement()?.prepare(sql)?;
+        for name in &variables {
+            let variable = ctx
+                .get_variable(name)
+                .ok_or_else(|| AppError::UndeclaredVariable(name.clone()))?;
+            statement.bind_parameter(Parameter::new(
+                ParamDirection::InputOutput,
+                match variable.type_ {
+                    VariableType::Char(_) => Type::Char,
+                    VariableType::NChar(_) => Type::NChar,
+                    VariableType::NVarChar(_) => Type::NVarChar,
+                    // numbers as text converted by the server, so no digits are lost
+                    VariableType::Number | VariableType::VarChar(_) => Type::VarChar,
+                },
+                variable.type_.size(),
+                variable.value.as_deref(),
+            ));
+        }
+        let (result, parameters) = statement.execute()?;
+        for (name, parameter) in variables.iter().zip(parameters) {
+            ctx.set_variable_value(name, parameter.value());
+        }
         Ok(result)
     }
 
     fn execute_sql_and_show(
         &self,
-        connection: &Connection,
+        ctx: &mut Context,
         sql: &str,
-        command: Option<&InternalCommand>,
-        less_enabled: bool,
+        expanded: Expanded,
     ) -> Result<(), AppError> {
-        let result = self.execute_sql(connection, sql);
+        let started = Instant::now();
+        let result = self.execute_sql(ctx, sql);
+        let executed = started.elapsed();
         match result {
-            Ok(result) => match self.show_result(result, command, less_enabled) {
-                Ok(_) => {}
-                Err(err) => println!("{err}"),
+            Ok(result) => match self.show_result(result, expanded, ctx) {
+                Ok(fetched) => {
+                    if ctx.timing() {
+                        self.show_timing(executed, fetched);
+                    }
+                }
+                Err(err) => {
+                    writeln!(self.output, "{err}");
+                    if let AppError::Client(err) = err {
+                        return self.on_sql_error(
+                            ctx,
+                            sql_error_code(&err),
+                            err.is_connection_lost(),
+                        );
+                    }
+                }
             },
-            Err(err) => self.print_execute_sql_error(err)?,
+            Err(AppError::Client(err)) => {
+                let (code, lost) = (sql_error_code(&err), err.is_connection_lost());
+                self.print_execute_sql_error(ctx, err)?;
+                return self.on_sql_error(ctx, code, lost);
+            }
+            Err(err) => return Err(err),
         }
         Ok(())
     }
+
+    /// Handles statements failed before reaching the database, like parse errors, as
+    /// `SQLERROR` with code 1. Returns the exit code if exiting by `WHENEVER`.
+    fn on_client_error(&self) -> Result<Option<i32>, AppError> {
+        match self.on_error(&self.context.read().unwrap(), ErrorCondition::SqlError, 1) {
+            Ok(()) => Ok(None),
+            Err(AppError::Exit(code)) => Ok(Some(code)),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Reconnects if the connection is lost and `SET RECONNECT ON`, then handles the error.
+    fn on_sql_error(&self, ctx: &mut Context, code: i32, lost: bool) -> Result<(), AppError> {
+        if lost {
+            if ctx.reconnect() {
+                writeln!(
+                    self.output,
+                    "Connection lost, the uncommitted transaction was rolled back. Reconnecting..."
+                );
+                self.reconnect(ctx)?;
+            } else {
+                writeln!(
+                    self.output,
+                    "Connection lost, try RECONNECT, or SET RECONNECT ON to reconnect automatically"
+                );
+            }
+        }
+        self.on_error(ctx, ErrorCondition::SqlError, code)
+    }
+
+    /// Handles error according to `WHENEVER` settings.
+    fn on_error(
+        &self,
+        ctx: &Context,
+        condition: ErrorCondition,
+        error_code: i32,
+    ) -> Result<(), AppError> {
+        let (exit_code, transaction) = match ctx.get_error_action(condition) {
+            ErrorAction::Exit(status, transaction) => (Some(status.code(error_code)), transaction),
+            ErrorAction::Continue(transaction) => (None, transaction),
+        };
+        if let Some(connection) = ctx.get_connection() {
+            let result = match transaction {
+                Transaction::Commit => connection.commit(),
+                Transaction::Rollback => connection.rollback(),
+                Transaction::None => Ok(()),
+            };
+            if let Err(err) = result {
+                self.print_execute_sql_error(ctx, err)?;
+            }
+        }
+        match exit_code {
+            Some(code) => Err(AppError::Exit(code)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A column of text for values which are not from a query.
+fn text_column(name: &str) -> Column {
+    Column {
+        display_size: name.len(),
+        name: name.to_string(),
+        size: 0,
+        type_: Type::VarChar,
+        nullable: true,
+        precision: 0,
+        scale: 0,
+        char_size: 0,
+        display_char_size: 0,
+    }
+}
+
+/// Formats like `00:00:01.234`.
+fn format_elapsed(elapsed: Duration) -> String {
+    let millis = elapsed.as_millis();
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+fn sql_error_code(err: &Error) -> i32 {
+    match err {
+        Error::YasClient(diag) => diag.code,
+        Error::Other | Error::Unexpected(_) => 1,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        io::{BufReader, Cursor},
+        rc::Rc,
+        sync::RwLock,
+    };
+
+    use super::{context::Context, input::BufReaderInput, App};
+
+    fn run(script: impl Into<String>) -> i32 {
+        let input = BufReaderInput::new(BufReader::new(Cursor::new(script.into())));
+        let context = Rc::new(RwLock::new(Context::default()));
+        App::new(Box::new(input), context).unwrap().run().unwrap()
+    }
+
+    #[test]
+    fn client_errors() {
+        assert_eq!(run("conn a/b@host:port\n"), 0);
+        assert_eq!(run("whenever sqlerror exit 3\nconn a/b@host:port\n"), 3);
+        assert_eq!(run("whenever sqlerror exit 3\nprint :x\n"), 3);
+    }
+
+    #[test]
+    fn variables() {
+        let path = std::env::temp_dir().join(format!("yasqlplus-{}.lst", std::process::id()));
+        let script = format!(
+            "set markup csv on\nspool {}\nvar n number = 1.5\nvar s varchar(8)\nvar n\nprint\nspool off\n",
+            path.display()
+        );
+        assert_eq!(run(script), 0);
+        let spooled = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        // commands are spooled too
+        let output = spooled
+            .lines()
+            .filter(|x| !x.starts_with("SQL > "))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            output,
+            [
+                "\"Name\",\"Type\"",
+                "\"N\",\"NUMBER\"",
+                "\"N\",\"S\"",
+                "1.5,"
+            ]
+        );
+    }
 }
@@ -1,21 +1,25 @@
-use std::{cell::RefCell, rc::Rc, sync::RwLock};
+use std::{collections::HashSet, rc::Rc, sync::RwLock};
 
-use rustyline::completion::{Candidate, Completer};
+use rustyline::completion::{Candidate, Completer, FilenameCompleter};
 
-use super::context::Context;
+use crate::command::{COMMANDS, SETTINGS};
+
+use super::{
+    context::Context,
+    keywords::{FUNCTIONS, KEYWORDS},
+    tokenizer::{analyze, Expect, TableRef},
+};
 
 pub struct YspCompleter {
     connection: Rc<RwLock<Context>>,
-    tables: RefCell<Vec<String>>,
-    views: RefCell<Vec<String>>,
+    filename: FilenameCompleter,
 }
 
 impl YspCompleter {
     pub fn new(connection: Rc<RwLock<Context>>) -> Self {
         Self {
             connection,
-            tables: Default::default(),
-            views: Default::default(),
+            filename: FilenameCompleter::new(),
         }
     }
 }
@@ -24,44 +28,33 @@ impl Completer for YspCompleter {
     type Candidate = YspCandidate;
 
     fn complete(
-        &self, // FIXME should be `&mut self`
+        &self,
         line: &str,
         pos: usize,
-        ctx: &rustyline::Context<'_>,
+        _ctx: &rustyline::Context<'_>,
     ) -> rustyline::Result<(usize, Vec<Self::Candidate>)> {
-        let _ = (line, pos, ctx);
-        let mut results = vec![];
-        if let Some(trailing) = line
-            .trim_end_matches(';')
-            .to_uppercase()
-            .strip_prefix("SELECT * FROM ")
-        {
-            if let Some((tables_or_views, existed_columns)) = trailing.split_once("WHERE") {
-                for table_or_view in tables_or_views.split(',').map(|x| x.trim()) {
-                    results.extend(self.get_columns(
-                        table_or_view,
-                        existed_columns.split([' ']).last().unwrap_or_default(),
-                    ));
+        if let Some(result) = self.complete_command(line, pos) {
+            return result;
+        }
+        let analysis = analyze(line, pos);
+        let candidates = match analysis.expect {
+            Expect::Nothing => vec![],
+            Expect::Keyword if line[..analysis.start].trim().is_empty() => COMMANDS
+                .iter()
+                .map(|x| YspCandidate::Command(x.to_string()))
+                .chain(keywords())
+                .collect(),
+            Expect::Keyword => keywords(),
+            Expect::Table { schema } => self.get_tables(schema.as_deref()),
+            Expect::Column { tables, qualified } => {
+                let mut candidates = self.get_columns(&tables);
+                if !qualified {
+                    candidates.extend(keywords());
                 }
-            } else {
-                results.extend(self.complete_query(trailing));
+                candidates
             }
-        } else if let Some(trailing) = line
-            .trim_end_matches(';')
-            .to_uppercase()
-            .strip_prefix("DESC ")
-        {
-            results.extend(self.complete_query(trailing));
-        } else {
-            results.extend(
-                keywords()
-                    .iter()
-                    .map(|x| x.to_string())
-                    .map(YspCandidate::Keyword),
-            );
-        }
-
-        Ok((pos, results))
+        };
+        Ok((analysis.start, filter(candidates, &analysis.word)))
     }
 
     fn update(
@@ -77,106 +70,291 @@ impl Completer for YspCompleter {
 }
 
 impl YspCompleter {
-    pub fn complete_query(&self, trailing: &str) -> Vec<YspCandidate> {
-        let mut results = vec![];
-        if self.tables.take().is_empty() {
-            self.tables
-                .replace(self.get_tables_or_views("select table_name from dba_tables"));
+    /// Completes arguments of internal commands, and paths of shell commands and scripts.
+    /// `None` for SQL.
+    fn complete_command(
+        &self,
+        line: &str,
+        pos: usize,
+    ) -> Option<rustyline::Result<(usize, Vec<YspCandidate>)>> {
+        let head = &line[..pos];
+        if head.contains('\n') {
+            return None;
         }
-        if self.views.take().is_empty() {
-            self.views
-                .replace(self.get_tables_or_views("select view_name from dba_views"));
+        let head = head.trim_start();
+        if head.starts_with(['!', '@']) {
+            return Some(self.complete_path(line, pos));
         }
-
-        let prefix = trailing
-            .split(' ')
-            .last()
-            .unwrap_or_default()
-            .to_uppercase();
-        results.extend(
-            self.tables
-                .borrow()
+        let mut start = head
+            .rfind(char::is_whitespace)
+            .map_or(pos - head.len(), |x| pos - head.len() + x + 1);
+        let words = line[pos - head.len()..start]
+            .split_whitespace()
+            .collect::<Vec<_>>();
+        let (command, args) = words.split_first()?;
+        let mut word = &line[start..pos];
+        let candidates = match (command.to_lowercase().as_str(), args) {
+            ("spool" | "spo", []) => return Some(self.complete_path(line, pos)),
+            ("spool" | "spo", [_]) => settings(&["CREATE", "REPLACE", "APPEND"]),
+            ("set", []) => SETTINGS
                 .iter()
-                .filter(|x| x.starts_with(&prefix))
-                .map(|x| YspCandidate::Table(x[prefix.len()..].to_string())),
-        );
-        results.extend(
-            self.views
-                .take()
-                .iter()
-                .filter(|x| x.starts_with(&prefix))
-                .map(|x| YspCandidate::View(x[prefix.len()..].to_string())),
-        );
-        results
+                .map(|x| YspCandidate::Setting(x.0.to_string()))
+                .collect(),
+            ("set", [option]) => {
+                let option = option.to_uppercase();
+                SETTINGS
+                    .iter()
+                    .find(|x| x.0.starts_with(&option))
+                    .map(|x| settings(x.1))
+                    .unwrap_or_default()
+            }
+            // profiles are referred like hosts
+            ("conn", _) => match word.rsplit_once('@') {
+                Some((_, profile)) => {
+                    start = pos - profile.len();
+                    word = profile;
+                    let context = self.connection.read().unwrap();
+                    context
+                        .profiles()
+                        .keys()
+                        .map(|x| YspCandidate::Profile(x.clone()))
+                        .collect()
+                }
+                None => vec![],
+            },
+            _ => return None,
+        };
+        Some(Ok((start, filter(candidates, word))))
     }
 
-    fn get_tables_or_views(&self, sql: &str) -> Vec<String> {
-        if let Some(connection) = self.connection.read().unwrap().get_connection() {
-            if let Ok(stmt) = connection.create_statement() {
-                if let Ok(result) = stmt.execute_sql(sql) {
-                    return result
-                        .result_set()
-                        .rows()
-                        .map(|r| match r[0].as_ref().unwrap() {
-                            yasqlplus_client::wrapper::Value::VarChar(t) => t.clone(),
-                            _ => unreachable!(),
-                        })
-                        .collect::<Vec<_>>();
-                }
-            }
+    fn complete_path(
+        &self,
+        line: &str,
+        pos: usize,
+    ) -> rustyline::Result<(usize, Vec<YspCandidate>)> {
+        let (start, pairs) = self.filename.complete_path(line, pos)?;
+        Ok((
+            start,
+            pairs
+                .into_iter()
+                .map(|x| YspCandidate::Path {
+                    display: x.display,
+                    replacement: x.replacement,
+                })
+                .collect(),
+        ))
+    }
+
+    /// Tables and views in the schema, or the current schema and schema names if `None`.
+    fn get_tables(&self, schema: Option<&str>) -> Vec<YspCandidate> {
+        let context = self.connection.read().unwrap();
+        let (Some(session), Some(connection)) = (context.session(), context.get_connection())
+        else {
+            return vec![];
+        };
+        let metadata = &session.metadata;
+        let Some(owner) = schema
+            .map(str::to_string)
+            .or_else(|| metadata.current_schema(connection))
+        else {
+            return vec![];
+        };
+        let mut candidates = metadata
+            .objects(&owner)
+            .into_iter()
+            .map(|x| match x.type_.as_str() {
+                "VIEW" => YspCandidate::View(x.name),
+                _ => YspCandidate::Table(x.name),
+            })
+            .collect::<Vec<_>>();
+        if schema.is_none() {
+            candidates.extend(metadata.schemas().into_iter().map(YspCandidate::Schema));
         }
-        vec![]
+        candidates
     }
 
-    fn get_columns(&self, table_or_view: &str, prefix: &str) -> Vec<YspCandidate> {
-        if let Some(connection) = self.connection.read().unwrap().get_connection() {
-            if let Ok(stmt) = connection.create_statement() {
-                if let Ok(result) =
-                    stmt.execute_sql(&format!("select * from {table_or_view} where 1 = 2"))
-                {
-                    return result
-                        .result_set()
-                        .iter_columns()
-                        .map(|r| r.name)
-                        .filter(|x| x.starts_with(prefix))
-                        .map(|x| x[prefix.len()..].to_string())
-                        .map(YspCandidate::Column)
-                        .collect::<Vec<_>>();
+    fn get_columns(&self, tables: &[TableRef]) -> Vec<YspCandidate> {
+        let context = self.connection.read().unwrap();
+        let (Some(session), Some(connection)) = (context.session(), context.get_connection())
+        else {
+            return vec![];
+        };
+        let metadata = &session.metadata;
+        let current_schema = metadata.current_schema(connection);
+        let mut candidates: Vec<YspCandidate> = vec![];
+        for table in tables {
+            let Some(owner) = table.schema.clone().or_else(|| current_schema.clone()) else {
+                continue;
+            };
+            for column in metadata.columns(connection, &owner, &table.name) {
+                let candidate = YspCandidate::Column(column);
+                if !candidates.contains(&candidate) {
+                    candidates.push(candidate);
                 }
             }
         }
-        vec![]
+        candidates
     }
 }
 
-pub fn keywords() -> Vec<&'static str> {
-    // TODO
-    vec!["SELECT", "CREATE", "INSERT"]
+fn settings(values: &[&str]) -> Vec<YspCandidate> {
+    values
+        .iter()
+        .map(|x| YspCandidate::Setting(x.to_string()))
+        .collect()
+}
+
+fn keywords() -> Vec<YspCandidate> {
+    KEYWORDS
+        .iter()
+        .map(|x| YspCandidate::Keyword(x.to_string()))
+        .chain(
+            FUNCTIONS
+                .iter()
+                .map(|x| YspCandidate::Function(x.to_string())),
+        )
+        .collect()
+}
+
+/// Candidates starting with the word case-insensitively, in lower case if the word is.
+///
+/// Candidates completed the same, e.g. a column named like a keyword, are kept once, the first.
+fn filter(candidates: Vec<YspCandidate>, word: &str) -> Vec<YspCandidate> {
+    let prefix = word.trim_start_matches('"').to_uppercase();
+    let lower = !word.is_empty() && word.chars().all(|x| !x.is_uppercase());
+    let mut seen = HashSet::new();
+    candidates
+        .into_iter()
+        .filter(|x| x.display().to_uppercase().starts_with(&prefix))
+        .map(|x| if lower { x.into_lowercase() } else { x })
+        .filter(|x| seen.insert(x.replacement().to_string()))
+        .collect()
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum YspCandidate {
     Keyword(String),
+    Function(String),
+    Schema(String),
     Table(String),
     View(String),
     Column(String),
+    /// Internal commands, which are lower case.
+    Command(String),
+    /// Options of `SET` and their values.
+    Setting(String),
+    /// Connection profiles, case sensitive.
+    Profile(String),
+    Path {
+        display: String,
+        replacement: String,
+    },
 }
 
-impl Candidate for YspCandidate {
-    fn display(&self) -> &str {
+impl YspCandidate {
+    fn into_lowercase(self) -> Self {
         match self {
-            YspCandidate::Keyword(v) => v,
-            YspCandidate::Table(v) => v,
-            YspCandidate::View(v) => v,
-            YspCandidate::Column(v) => v,
+            YspCandidate::Keyword(v) => YspCandidate::Keyword(v.to_lowercase()),
+            YspCandidate::Function(v) => YspCandidate::Function(v.to_lowercase()),
+            YspCandidate::Schema(v) => YspCandidate::Schema(v.to_lowercase()),
+            YspCandidate::Table(v) => YspCandidate::Table(v.to_lowercase()),
+            YspCandidate::View(v) => YspCandidate::View(v.to_lowercase()),
+            YspCandidate::Column(v) => YspCandidate::Column(v.to_lowercase()),
+            YspCandidate::Setting(v) => YspCandidate::Setting(v.to_lowercase()),
+            YspCandidate::Command(_) | YspCandidate::Profile(_) | YspCandidate::Path { .. } => self,
         }
     }
+}
 
-    fn replacement(&self) -> &str {
+impl Candidate for YspCandidate {
+    fn display(&self) -> &str {
         match self {
             YspCandidate::Keyword(v)
+            | YspCandidate::Function(v)
+            | YspCandidate::Schema(v)
             | YspCandidate::Table(v)
             | YspCandidate::View(v)
-            | YspCandidate::Column(v) => v,
+            | YspCandidate::Column(v)
+            | YspCandidate::Command(v)
+            | YspCandidate::Setting(v)
+            | YspCandidate::Profile(v) => v,
+            YspCandidate::Path { display, .. } => display,
         }
     }
+
+    fn replacement(&self) -> &str {
+        match self {
+            YspCandidate::Path { replacement, .. } => replacement,
+            _ => self.display(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::BTreeMap, rc::Rc, sync::RwLock};
+
+    use crate::{app::context::Context, config::Profile};
+
+    use super::{filter, YspCandidate, YspCompleter};
+
+    fn complete(completer: &YspCompleter, line: &str) -> Option<(usize, Vec<YspCandidate>)> {
+        completer
+            .complete_command(line, line.len())
+            .map(|x| x.unwrap())
+    }
+
+    #[test]
+    fn duplicates() {
+        let candidates = vec![
+            YspCandidate::Column("NAME".to_string()),
+            YspCandidate::Keyword("NOT".to_string()),
+            YspCandidate::Table("NAME".to_string()),
+            YspCandidate::Keyword("NAME".to_string()),
+            YspCandidate::Column("NOT".to_string()),
+        ];
+        assert_eq!(
+            filter(candidates, "n"),
+            vec![
+                YspCandidate::Column("name".to_string()),
+                YspCandidate::Keyword("not".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn commands() {
+        let mut context = Context::default();
+        context.set_profiles(BTreeMap::from([
+            ("prod".to_string(), Profile::default()),
+            ("Dev".to_string(), Profile::default()),
+        ]));
+        let completer = YspCompleter::new(Rc::new(RwLock::new(context)));
+
+        assert_eq!(
+            complete(&completer, "set mark"),
+            Some((4, vec![YspCandidate::Setting("markup".to_string())]))
+        );
+        assert_eq!(
+            complete(&completer, "SET PAGER A"),
+            Some((
+                10,
+                vec![
+                    YspCandidate::Setting("ALWAYS".to_string()),
+                    YspCandidate::Setting("AUTO".to_string()),
+                ]
+            ))
+        );
+        assert_eq!(
+            complete(&completer, "spool out.txt ap"),
+            Some((14, vec![YspCandidate::Setting("append".to_string())]))
+        );
+        assert_eq!(
+            complete(&completer, "conn sys@d"),
+            Some((9, vec![YspCandidate::Profile("Dev".to_string())]))
+        );
+        assert_eq!(complete(&completer, "conn sys"), Some((5, vec![])));
+        assert_eq!(complete(&completer, "select * from t"), None);
+        assert_eq!(complete(&completer, "sel"), None);
+    }
 }
@@ -1,40 +1,185 @@
+use std::{collections::BTreeMap, time::Instant};
+
+use crate::config::Profile;
+
+use super::{
+    metadata::Metadata,
+    theme::{Theme, ThemeError},
+};
+
 use colored::Colorize;
 use yasqlplus_client::wrapper::Connection;
 
-use crate::command::Command;
+use crate::command::{
+    ColumnOption, ColumnSettings, Command, CompletionMode, EditMode, ErrorAction, ErrorCondition,
+    Expanded, PagerMode, ResultFormat, Setting, Variable,
+};
+
+const DEFAULT_PROMPT: &str = "{username}@{host}:{port} > ";
+
+/// Name of the session connected by `conn` without `-n`.
+pub const DEFAULT_SESSION: &str = "default";
+
+pub struct Session {
+    /// `None` after `DISCONNECT`.
+    pub connection: Option<Connection>,
+    pub username: String,
+    pub host: String,
+    pub port: u16,
+    /// Kept for `RECONNECT`.
+    pub password: String,
+    /// To initialize the session once connected.
+    pub profile: Option<Profile>,
+    /// Names for completion.
+    pub metadata: Metadata,
+}
+
+impl Session {
+    pub fn refresh_metadata(&self) {
+        self.metadata
+            .refresh(&self.host, self.port, &self.username, &self.password);
+    }
+}
 
 #[derive(Default)]
 pub struct Context {
-    connection: Option<Connection>,
-    prompt_conn: String,
+    sessions: BTreeMap<String, Session>,
+    current_session: Option<String>,
+    prompt_template: Option<String>,
     last_command: Option<Command>,
     need_echo: bool,
-    less_enabled: bool,
+    pager_mode: PagerMode,
+    pager: Option<String>,
+    variables: BTreeMap<String, Variable>,
+    sql_error_action: ErrorAction,
+    os_error_action: ErrorAction,
+    result_format: ResultFormat,
+    pagesize: usize,
+    expanded: Expanded,
+    /// Overrides `expanded` for the next statement only.
+    next_expanded: Option<Expanded>,
+    columns: BTreeMap<String, ColumnSettings>,
+    truncate: bool,
+    max_column_width: usize,
+    timing: bool,
+    reconnect: bool,
+    theme: Theme,
+    edit_mode: EditMode,
+    completion: CompletionMode,
+    /// Started by `TIMING START`, latest at last.
+    timers: Vec<(Option<String>, Instant)>,
+    profiles: BTreeMap<String, Profile>,
 }
 
 impl Context {
     pub fn get_prompt(&self) -> String {
-        if self.connection.is_none() {
-            "SQL > ".to_owned()
+        let (Some(name), Some(session)) = (
+            self.session_name(),
+            self.session().filter(|x| x.connection.is_some()),
+        ) else {
+            return "SQL > ".to_owned();
+        };
+        let template = self.prompt_template();
+        let prompt = template
+            .replace("{session}", name)
+            .replace("{username}", &session.username)
+            .replace("{host}", &session.host)
+            .replace("{port}", &session.port.to_string());
+        let color = self.theme.palette().prompt;
+        if name == DEFAULT_SESSION || template.contains("{session}") {
+            prompt.color(color).to_string()
         } else {
-            self.prompt_conn.green().to_string()
+            format!("[{name}] {prompt}").color(color).to_string()
+        }
+    }
+
+    /// Prompt when connected, `{session}`, `{username}`, `{host}` and `{port}` will be replaced.
+    pub fn prompt_template(&self) -> &str {
+        self.prompt_template.as_deref().unwrap_or(DEFAULT_PROMPT)
+    }
+
+    pub fn set_prompt_template(&mut self, prompt_template: String) {
+        self.prompt_template = Some(prompt_template);
+    }
+
+    pub fn profiles(&self) -> &BTreeMap<String, Profile> {
+        &self.profiles
+    }
+
+    pub fn set_profiles(&mut self, profiles: BTreeMap<String, Profile>) {
+        self.profiles = profiles;
+    }
+
+    /// Fails only if the theme could not be loaded.
+    pub fn apply_setting(&mut self, setting: &Setting) -> Result<(), ThemeError> {
+        match setting {
+            Setting::Markup(format) => self.set_result_format(*format),
+            Setting::PageSize(pagesize) => self.set_pagesize(*pagesize),
+            Setting::Expanded(expanded) => self.set_expanded(*expanded),
+            Setting::Wrap(wrap) => self.set_truncate(!wrap),
+            Setting::MaxColumnWidth(width) => self.set_max_column_width(*width),
+            Setting::Pager(mode) => self.set_pager_mode(*mode),
+            Setting::PagerCommand(pager) => self.set_pager(pager.clone()),
+            Setting::Timing(timing) => self.set_timing(*timing),
+            Setting::Reconnect(reconnect) => self.set_reconnect(*reconnect),
+            Setting::Theme(theme) => self.set_theme(Theme::load(theme)?),
+            Setting::EditMode(mode) => self.set_edit_mode(*mode),
+            Setting::Completion(mode) => self.set_completion(*mode),
         }
+        Ok(())
+    }
+
+    pub fn get_connection(&self) -> Option<&Connection> {
+        self.session()?.connection.as_ref()
+    }
+
+    pub fn session(&self) -> Option<&Session> {
+        self.sessions.get(self.current_session.as_deref()?)
+    }
+
+    pub fn session_name(&self) -> Option<&str> {
+        self.current_session.as_deref()
+    }
+
+    pub fn sessions(&self) -> &BTreeMap<String, Session> {
+        &self.sessions
     }
 
-    pub fn set_prompt(&mut self, prompt: String) {
-        self.prompt_conn = prompt;
+    /// Adds the session, replacing the one with the same name, and switches to it.
+    pub fn add_session(&mut self, name: String, session: Session) {
+        self.sessions.insert(name.clone(), session);
+        self.current_session = Some(name);
     }
 
-    pub fn get_connection(&self) -> &Option<Connection> {
-        &self.connection
+    /// Returns `false` if the session is not open.
+    pub fn use_session(&mut self, name: &str) -> bool {
+        let found = self.sessions.contains_key(name);
+        if found {
+            self.current_session = Some(name.to_string());
+        }
+        found
     }
 
-    pub fn set_connection(&mut self, conn: Option<Connection>) {
-        self.connection = conn;
+    /// Drops the connection of the current session, returns `false` if not connected.
+    pub fn disconnect(&mut self) -> bool {
+        let name = self.current_session.as_deref().unwrap_or_default();
+        self.sessions
+            .get_mut(name)
+            .and_then(|x| x.connection.take())
+            .is_some()
     }
 
-    pub fn get_command(&self) -> &Option<Command> {
-        &self.last_command
+    /// Disconnects the session, leaving no current session if it is the current one.
+    pub fn close_session(&mut self, name: &str) -> bool {
+        if self.current_session.as_deref() == Some(name) {
+            self.current_session = None;
+        }
+        self.sessions.remove(name).is_some()
+    }
+
+    /// Last SQL statement, edited by `EDIT`.
+    pub fn get_command(&self) -> Option<&Command> {
+        self.last_command.as_ref()
     }
 
     pub fn set_command(&mut self, command: Option<Command>) {
@@ -49,11 +194,192 @@ impl Context {
         self.need_echo = need_echo;
     }
 
-    pub fn less_enabled(&self) -> bool {
-        self.less_enabled
+    pub fn pager_mode(&self) -> PagerMode {
+        self.pager_mode
+    }
+
+    pub fn set_pager_mode(&mut self, pager_mode: PagerMode) {
+        self.pager_mode = pager_mode;
+    }
+
+    /// Pager set by `SET PAGER`, or `$YASQLPLUS_PAGER`, `$PAGER`, `less` in order.
+    pub fn pager(&self) -> String {
+        self.pager
+            .clone()
+            .or_else(|| std::env::var("YASQLPLUS_PAGER").ok())
+            .or_else(|| std::env::var("PAGER").ok())
+            .filter(|x| !x.trim().is_empty())
+            .unwrap_or_else(|| "less".to_string())
+    }
+
+    pub fn set_pager(&mut self, pager: String) {
+        self.pager = Some(pager);
+    }
+
+    pub fn result_format(&self) -> ResultFormat {
+        self.result_format
+    }
+
+    pub fn set_result_format(&mut self, result_format: ResultFormat) {
+        self.result_format = result_format;
+    }
+
+    pub fn pagesize(&self) -> usize {
+        self.pagesize
+    }
+
+    pub fn set_pagesize(&mut self, pagesize: usize) {
+        self.pagesize = pagesize;
+    }
+
+    pub fn expanded(&self) -> Expanded {
+        self.expanded
+    }
+
+    pub fn set_expanded(&mut self, expanded: Expanded) {
+        self.expanded = expanded;
+    }
+
+    pub fn set_next_expanded(&mut self, expanded: Option<Expanded>) {
+        self.next_expanded = expanded;
+    }
+
+    /// `SET EXPANDED` for the statement to execute, the override for it is cleared.
+    pub fn take_expanded(&mut self) -> Expanded {
+        self.next_expanded.take().unwrap_or(self.expanded)
+    }
+
+    /// Whether to truncate values wider than their columns, `SET WRAP OFF`.
+    pub fn truncate(&self) -> bool {
+        self.truncate
+    }
+
+    pub fn set_truncate(&mut self, truncate: bool) {
+        self.truncate = truncate;
+    }
+
+    pub fn max_column_width(&self) -> usize {
+        self.max_column_width
+    }
+
+    pub fn set_max_column_width(&mut self, max_column_width: usize) {
+        self.max_column_width = max_column_width;
+    }
+
+    pub fn timing(&self) -> bool {
+        self.timing
+    }
+
+    pub fn set_timing(&mut self, timing: bool) {
+        self.timing = timing;
+    }
+
+    /// Whether to connect again when the connection is lost, `SET RECONNECT`.
+    pub fn reconnect(&self) -> bool {
+        self.reconnect
+    }
+
+    pub fn set_reconnect(&mut self, reconnect: bool) {
+        self.reconnect = reconnect;
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+    }
+
+    pub fn edit_mode(&self) -> EditMode {
+        self.edit_mode
+    }
+
+    pub fn set_edit_mode(&mut self, edit_mode: EditMode) {
+        self.edit_mode = edit_mode;
+    }
+
+    pub fn completion(&self) -> CompletionMode {
+        self.completion
+    }
+
+    pub fn set_completion(&mut self, completion: CompletionMode) {
+        self.completion = completion;
+    }
+
+    pub fn timers(&self) -> &[(Option<String>, Instant)] {
+        &self.timers
+    }
+
+    pub fn start_timer(&mut self, name: Option<String>) {
+        self.timers.push((name, Instant::now()));
+    }
+
+    /// Finds the timer by name, the latest one if `None`.
+    pub fn get_timer(&self, name: Option<&str>) -> Option<&(Option<String>, Instant)> {
+        match name {
+            Some(name) => self.timers.iter().rfind(|x| x.0.as_deref() == Some(name)),
+            None => self.timers.last(),
+        }
+    }
+
+    pub fn stop_timer(&mut self, name: Option<&str>) -> Option<(Option<String>, Instant)> {
+        let index = match name {
+            Some(name) => self
+                .timers
+                .iter()
+                .rposition(|x| x.0.as_deref() == Some(name))?,
+            None => self.timers.len().checked_sub(1)?,
+        };
+        Some(self.timers.remove(index))
+    }
+
+    pub fn columns(&self) -> &BTreeMap<String, ColumnSettings> {
+        &self.columns
+    }
+
+    /// Applies options to the column, on top of previous ones.
+    pub fn define_column(&mut self, name: &str, options: &[ColumnOption]) {
+        let settings = self.columns.entry(name.to_string()).or_default();
+        for option in options {
+            settings.apply(option.clone());
+        }
+    }
+
+    /// Returns `false` if the column is not defined.
+    pub fn clear_column(&mut self, name: &str) -> bool {
+        self.columns.remove(name).is_some()
+    }
+
+    pub fn get_variables(&self) -> &BTreeMap<String, Variable> {
+        &self.variables
+    }
+
+    pub fn get_variable(&self, name: &str) -> Option<&Variable> {
+        self.variables.get(name)
+    }
+
+    pub fn set_variable(&mut self, variable: Variable) {
+        self.variables.insert(variable.name.clone(), variable);
     }
 
-    pub fn set_less_enabled(&mut self, less_enabled: bool) {
-        self.less_enabled = less_enabled;
+    pub fn set_variable_value(&mut self, name: &str, value: Option<String>) {
+        if let Some(variable) = self.variables.get_mut(name) {
+            variable.value = value;
+        }
+    }
+
+    pub fn get_error_action(&self, condition: ErrorCondition) -> ErrorAction {
+        match condition {
+            ErrorCondition::SqlError => self.sql_error_action,
+            ErrorCondition::OsError => self.os_error_action,
+        }
+    }
+
+    pub fn set_error_action(&mut self, condition: ErrorCondition, action: ErrorAction) {
+        match condition {
+            ErrorCondition::SqlError => self.sql_error_action = action,
+            ErrorCondition::OsError => self.os_error_action = action,
+        }
     }
 }
@@ -0,0 +1,335 @@
+use std::fmt::Display;
+
+use tabled::{builder::Builder, settings::Style};
+use yasqlplus_client::{
+    catalog::{
+        split_name, Catalog, Constraint, ConstraintType, Index, Sequence, Source, TableColumn,
+    },
+    wrapper::Error,
+};
+
+/// What `DESCRIBE` shows, read from the data dictionary.
+pub enum Description {
+    /// Tables and views.
+    Relation {
+        kind: String,
+        owner: String,
+        name: String,
+        comment: Option<String>,
+        columns: Vec<TableColumn>,
+        constraints: Vec<Constraint>,
+        indexes: Vec<Index>,
+    },
+    Sequence(Sequence),
+    /// Procedures, functions and packages.
+    Routines {
+        kind: String,
+        owner: String,
+        name: String,
+        routines: Vec<Routine>,
+    },
+}
+
+pub struct Routine {
+    /// `PROCEDURE` or `FUNCTION`.
+    pub kind: &'static str,
+    pub name: String,
+    /// Name, type and `IN`/`OUT`.
+    pub arguments: Vec<[String; 3]>,
+    pub returns: Option<String>,
+}
+
+/// Describes `[schema.]name`, `None` if no such object.
+///
+/// Without schema, the object is looked up in the current schema, then public synonyms.
+pub fn describe(source: &impl Source, name: &str) -> Result<Option<Description>, Error> {
+    let catalog = Catalog::new(source);
+    let (owner, name) = split_name(name);
+    let Some(mut object) = catalog.find_object(owner.as_deref(), &name)? else {
+        return Ok(None);
+    };
+    // synonyms of synonyms, but not in loops
+    for _ in 0..8 {
+        if object.type_ != "SYNONYM" {
+            break;
+        }
+        let Some(synonym) = catalog.synonym(&object.owner, &object.name)? else {
+            return Ok(None);
+        };
+        let Some(target) = catalog.find_object(Some(&synonym.table_owner), &synonym.table_name)?
+        else {
+            return Ok(None);
+        };
+        object = target;
+    }
+
+    let (owner, name, kind) = (object.owner, object.name, object.type_);
+    Ok(match kind.as_str() {
+        "TABLE" | "VIEW" => {
+            let (constraints, indexes) = if kind == "TABLE" {
+                (
+                    catalog
+                        .constraints(&owner, &name)?
+                        .into_iter()
+                        .filter(|x| x.type_ != ConstraintType::Check)
+                        .collect(),
+                    catalog.indexes(&owner, &name)?,
+                )
+            } else {
+                (vec![], vec![])
+            };
+            Some(Description::Relation {
+                comment: catalog.table_comment(&owner, &name)?,
+                columns: catalog.columns(&owner, &name)?,
+                constraints,
+                indexes,
+                kind,
+                owner,
+                name,
+            })
+        }
+        "SEQUENCE" => catalog.sequence(&owner, &name)?.map(Description::Sequence),
+        "PROCEDURE" | "FUNCTION" | "PACKAGE" => {
+            let mut routines: Vec<(String, Routine)> = vec![];
+            for argument in catalog.arguments(&owner, &name, kind == "PACKAGE")? {
+                let routine = match routines.last_mut() {
+                    Some((id, routine)) if *id == argument.subprogram_id => routine,
+                    _ => {
+                        routines.push((
+                            argument.subprogram_id.clone(),
+                            Routine {
+                                kind: "PROCEDURE",
+                                name: argument.subprogram.clone(),
+                                arguments: vec![],
+                                returns: None,
+                            },
+                        ));
+                        &mut routines.last_mut().unwrap().1
+                    }
+                };
+                match (argument.name, argument.data_type) {
+                    // the return value of a function
+                    (None, Some(data_type)) if argument.position == 0 => {
+                        routine.kind = "FUNCTION";
+                        routine.returns = Some(data_type);
+                    }
+                    (Some(name), data_type) => routine.arguments.push([
+                        name,
+                        data_type.unwrap_or_default(),
+                        argument.in_out,
+                    ]),
+                    // no arguments
+                    _ => {}
+                }
+            }
+            Some(Description::Routines {
+                kind,
+                owner,
+                name,
+                routines: routines.into_iter().map(|x| x.1).collect(),
+            })
+        }
+        _ => None,
+    })
+}
+
+impl Display for Description {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Description::Relation {
+                kind,
+                owner,
+                name,
+                comment,
+                columns,
+                constraints,
+                indexes,
+            } => {
+                write!(f, "{kind} {owner}.{name}")?;
+                if let Some(comment) = comment {
+                    write!(f, " -- {comment}")?;
+                }
+                let mut builder = Builder::default();
+                builder.push_record(["Name", "Null?", "Type", "Default", "Comment"]);
+                for column in columns {
+                    builder.push_record([
+                        column.name.clone(),
+                        if column.nullable { "" } else { "NOT NULL" }.to_string(),
+                        column.data_type.clone(),
+                        column.default.clone().unwrap_or_default(),
+                        column.comment.clone().unwrap_or_default(),
+                    ]);
+                }
+                write!(f, "\n{}", builder.build().with(Style::rounded()))?;
+
+                if !constraints.is_empty() {
+                    let mut builder = Builder::default();
+                    builder.push_record(["Constraint", "Type", "Columns", "References"]);
+                    for constraint in constraints {
+                        builder.push_record([
+                            constraint.name.clone(),
+                            match constraint.type_ {
+                                ConstraintType::PrimaryKey => "PRIMARY KEY",
+                                ConstraintType::Unique => "UNIQUE",
+                                ConstraintType::ForeignKey => "FOREIGN KEY",
+                                ConstraintType::Check => "CHECK",
+                            }
+                            .to_string(),
+                            constraint.columns.join(", "),
+                            constraint
+                                .references
+                                .as_ref()
+                                .map(|(table, columns)| format!("{table} ({})", columns.join(", ")))
+                                .unwrap_or_default(),
+                        ]);
+                    }
+                    write!(f, "\n{}", builder.build().with(Style::rounded()))?;
+                }
+
+                if !indexes.is_empty() {
+                    let mut builder = Builder::default();
+                    builder.push_record(["Index", "Unique", "Columns"]);
+                    for index in indexes {
+                        builder.push_record([
+                            index.name.clone(),
+                            if index.unique { "YES" } else { "NO" }.to_string(),
+                            index.columns.join(", "),
+                        ]);
+                    }
+                    write!(f, "\n{}", builder.build().with(Style::rounded()))?;
+                }
+                Ok(())
+            }
+            Description::Sequence(sequence) => {
+                let mut builder = Builder::default();
+                builder.push_record(["Min", &sequence.min_value]);
+                builder.push_record(["Max", &sequence.max_value]);
+                builder.push_record(["Increment", &sequence.increment_by]);
+                builder.push_record(["Cycle", if sequence.cycle { "YES" } else { "NO" }]);
+                builder.push_record(["Cache", &sequence.cache_size]);
+                builder.push_record(["Last", &sequence.last_number]);
+                write!(
+                    f,
+                    "SEQUENCE {}.{}\n{}",
+                    sequence.owner,
+                    sequence.name,
+                    builder.build().with(Style::rounded())
+                )
+            }
+            Description::Routines {
+                kind,
+                owner,
+                name,
+                routines,
+            } => {
+                write!(f, "{kind} {owner}.{name}")?;
+                for routine in routines {
+                    write!(f, "\n{} {}", routine.kind, routine.name)?;
+                    if let Some(returns) = &routine.returns {
+                        write!(f, " RETURN {returns}")?;
+                    }
+                    if !routine.arguments.is_empty() {
+                        let mut builder = Builder::default();
+                        builder.push_record(["Argument", "Type", "In/Out"]);
+                        for argument in &routine.arguments {
+                            builder.push_record(argument.clone());
+                        }
+                        write!(f, "\n{}", builder.build().with(Style::rounded()))?;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use yasqlplus_client::{catalog::Source, wrapper::Error};
+
+    use super::{describe, Description};
+
+    /// Answers queries by the closure.
+    struct Fake<F>(F);
+
+    impl<F: Fn(&str, &[&str]) -> Vec<Vec<Option<String>>>> Source for Fake<F> {
+        fn query(&self, sql: &str, parameters: &[&str]) -> Result<Vec<Vec<Option<String>>>, Error> {
+            Ok((self.0)(sql, parameters))
+        }
+    }
+
+    fn row(values: &[&str]) -> Vec<Option<String>> {
+        values.iter().map(|x| Some(x.to_string())).collect()
+    }
+
+    #[test]
+    fn synonyms() {
+        let objects = [
+            ("PUBLIC", "ORDERS", "SYNONYM"),
+            ("APP", "ORDERS_V", "SYNONYM"),
+            ("SALES", "ORDERS", "TABLE"),
+            ("APP", "PING", "SYNONYM"),
+            ("APP", "PONG", "SYNONYM"),
+        ];
+        let synonyms = [
+            ("PUBLIC", "ORDERS", "APP", "ORDERS_V"),
+            ("APP", "ORDERS_V", "SALES", "ORDERS"),
+            ("APP", "PING", "APP", "PONG"),
+            ("APP", "PONG", "APP", "PING"),
+        ];
+        let lookups = Cell::new(0);
+        // the current schema is `APP`
+        let source = Fake(|sql: &str, parameters: &[&str]| {
+            if sql.contains("_synonyms") {
+                lookups.set(lookups.get() + 1);
+                return synonyms
+                    .iter()
+                    .filter(|x| [x.0, x.1] == parameters)
+                    .map(|x| row(&[x.0, x.1, x.2, x.3]))
+                    .collect();
+            }
+            if sql.contains("_tab_columns") {
+                return vec![row(&["ID", "NUMBER", "22", "10", "0", "N", "", ""])];
+            }
+            if !sql.contains("_objects where owner") || sql.contains("1 = 0") {
+                return vec![];
+            }
+            let (owners, name) = match parameters {
+                [owner, name] => (vec![*owner], *name),
+                [name] => (vec!["APP", "PUBLIC"], *name),
+                _ => unreachable!(),
+            };
+            let mut found = objects
+                .iter()
+                .filter(|x| owners.contains(&x.0) && x.1 == name)
+                .collect::<Vec<_>>();
+            found.sort_by_key(|x| x.0 == "PUBLIC");
+            found.into_iter().map(|x| row(&[x.0, x.1, x.2])).collect()
+        });
+
+        let Some(Description::Relation {
+            kind,
+            owner,
+            name,
+            columns,
+            ..
+        }) = describe(&source, "orders").unwrap()
+        else {
+            panic!("a table expected");
+        };
+        assert_eq!(
+            (kind.as_str(), owner.as_str(), name.as_str()),
+            ("TABLE", "SALES", "ORDERS")
+        );
+        assert_eq!(columns[0].data_type, "NUMBER(10)");
+        assert_eq!(lookups.get(), 2);
+
+        // looping synonyms are given up after 8 hops
+        lookups.set(0);
+        assert!(describe(&source, "ping").unwrap().is_none());
+        assert_eq!(lookups.get(), 8);
+        assert!(describe(&source, "missing").unwrap().is_none());
+    }
+}
@@ -0,0 +1,87 @@
+use std::{
+    collections::hash_map::RandomState,
+    fs::{File, OpenOptions},
+    hash::{BuildHasher, Hasher},
+    io::{self, Write},
+    path::PathBuf,
+    process::Command,
+};
+
+/// Editor from `$VISUAL` or `$EDITOR`, `vi` if neither is set.
+pub fn editor() -> String {
+    std::env::var("VISUAL")
+        .ok()
+        .or_else(|| std::env::var("EDITOR").ok())
+        .filter(|x| !x.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+/// Opens `text` in the editor with a temporary file, and returns it once the editor exits.
+pub fn edit(text: &str) -> io::Result<String> {
+    let (path, mut file) = create_temp_file()?;
+    let written = file.write_all(text.as_bytes());
+    drop(file);
+    let edited = written
+        .and_then(|_| edit_file(&path.to_string_lossy()))
+        .and_then(|_| std::fs::read_to_string(&path));
+    let _ = std::fs::remove_file(&path);
+    edited
+}
+
+/// Creates a file only the current user could read, under an unpredictable name which
+/// doesn't exist yet, so it can't be a link planted by others in a shared directory.
+fn create_temp_file() -> io::Result<(PathBuf, File)> {
+    loop {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u32(std::process::id());
+        let path = std::env::temp_dir().join(format!("yasqlplus-{:016x}.sql", hasher.finish()));
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        match options.open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Runs the editor on the file, by the shell so the editor could have arguments like `code -w`.
+pub fn edit_file(path: &str) -> io::Result<()> {
+    let editor = editor();
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{editor} \"$1\""))
+        .arg("sh")
+        .arg(path)
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("`{editor}` exited with {status}")))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::create_temp_file;
+
+    #[test]
+    fn temp_file() {
+        let (path, _) = create_temp_file().unwrap();
+        let (other, _) = create_temp_file().unwrap();
+        assert_ne!(path, other);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        std::fs::remove_file(path).unwrap();
+        std::fs::remove_file(other).unwrap();
+    }
+}
@@ -0,0 +1,284 @@
+use colored::Colorize;
+use yasqlplus_client::wrapper::{Column, Value};
+
+use crate::app::theme::Palette;
+
+use super::{fit, is_numeric, ResultFormatter};
+
+/// Rows kept to compute widths of columns before streaming the rest.
+const SAMPLE_ROWS: usize = 100;
+
+/// Columns not wider than this will be as wide as their display size when streaming,
+/// so that most values fetched after the sample still fit.
+const MAX_DISPLAY_SIZE: usize = 32;
+
+/// Text columns won't be shrunk narrower than this to fit the terminal.
+const MIN_WIDTH: usize = 8;
+
+/// How to fit columns into limited width.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Fitting {
+    /// Width of the terminal, text columns will be shrunk to fit in.
+    pub width: Option<usize>,
+    /// Max width of text columns, `0` for unlimited.
+    pub max_column_width: usize,
+    /// Truncates values wider than their columns with `…` instead of wrapping them.
+    pub truncate: bool,
+}
+
+pub struct Cell {
+    pub content: String,
+    pub null: bool,
+    pub numeric: bool,
+    /// Name of the column rather than a value.
+    pub heading: bool,
+}
+
+impl Cell {
+    pub fn new(value: &Option<Value>) -> Self {
+        match value {
+            Some(value) => Self {
+                content: value.to_string(),
+                null: false,
+                numeric: is_numeric(value),
+                heading: false,
+            },
+            None => Self {
+                content: "<null>".to_string(),
+                null: true,
+                numeric: false,
+                heading: false,
+            },
+        }
+    }
+
+    pub fn heading(name: &str) -> Self {
+        Self {
+            content: name.to_string(),
+            null: false,
+            numeric: false,
+            heading: true,
+        }
+    }
+}
+
+/// How to draw rows aligned by widths of columns.
+pub trait Layout {
+    /// Heading before the first row.
+    fn heading(&self, headings: &[String], widths: &[usize]) -> String;
+
+    /// Heading repeated every `pagesize` rows.
+    fn repeated_heading(&self, headings: &[String], widths: &[usize]) -> String;
+
+    fn row(&self, cells: &[Cell], widths: &[usize]) -> String;
+
+    /// Output after all rows.
+    fn end(&self, widths: &[usize]) -> String;
+
+    /// Width taken by borders and separators of a row.
+    fn overhead(&self, columns: usize) -> usize;
+}
+
+/// Keeps a sample of rows to compute widths of columns, streams the rest.
+pub struct AlignedFormatter<L: Layout> {
+    layout: L,
+    pagesize: usize,
+    fitting: Fitting,
+    headings: Vec<String>,
+    display_sizes: Vec<usize>,
+    sample: Vec<Vec<Cell>>,
+    widths: Option<Vec<usize>>,
+    /// Rows since last heading.
+    page_rows: usize,
+}
+
+impl<L: Layout> AlignedFormatter<L> {
+    /// Heading will be repeated every `pagesize` rows, `0` for never.
+    pub fn new(layout: L, pagesize: usize, fitting: Fitting) -> Self {
+        Self {
+            layout,
+            pagesize,
+            fitting,
+            headings: vec![],
+            display_sizes: vec![],
+            sample: vec![],
+            widths: None,
+            page_rows: 0,
+        }
+    }
+
+    fn compute_widths(&self, streaming: bool) -> Vec<usize> {
+        let mut widths = self
+            .headings
+            .iter()
+            .enumerate()
+            .map(|(x, heading)| {
+                let width = self
+                    .sample
+                    .iter()
+                    .flat_map(|row| row[x].content.lines())
+                    .map(console::measure_text_width)
+                    .chain([console::measure_text_width(heading)])
+                    .max()
+                    .unwrap_or_default();
+                let width = match self.display_sizes[x] {
+                    size if streaming && size <= MAX_DISPLAY_SIZE => width.max(size),
+                    _ => width,
+                };
+                width.max(1)
+            })
+            .collect::<Vec<_>>();
+
+        // numeric columns are kept intact
+        let shrinkable = (0..widths.len())
+            .map(|x| {
+                !(self.sample.iter().any(|row| row[x].numeric)
+                    && self.sample.iter().all(|row| row[x].null || row[x].numeric))
+            })
+            .collect::<Vec<_>>();
+        if self.fitting.max_column_width > 0 {
+            for (width, _) in widths.iter_mut().zip(&shrinkable).filter(|(_, x)| **x) {
+                *width = (*width).min(self.fitting.max_column_width);
+            }
+        }
+        if let Some(width) = self.fitting.width {
+            let available = width.saturating_sub(self.layout.overhead(widths.len()));
+            while widths.iter().sum::<usize>() > available {
+                let widest = widths
+                    .iter()
+                    .enumerate()
+                    .filter(|(x, width)| shrinkable[*x] && **width > MIN_WIDTH)
+                    .max_by_key(|(_, width)| **width);
+                match widest {
+                    Some((x, _)) => widths[x] -= 1,
+                    None => break,
+                }
+            }
+        }
+        widths
+    }
+
+    fn fit_headings(&self, widths: &[usize]) -> Vec<String> {
+        self.headings
+            .iter()
+            .zip(widths)
+            .map(|(heading, width)| fit(heading, *width, self.fitting.truncate.then_some("…")))
+            .collect()
+    }
+
+    /// Outputs heading and rows in sample.
+    fn flush_sample(&mut self, streaming: bool) -> String {
+        let widths = self.compute_widths(streaming);
+        let mut output = self.layout.heading(&self.fit_headings(&widths), &widths);
+        for row in std::mem::take(&mut self.sample) {
+            output.push_str(&self.render_row(row, &widths));
+        }
+        self.widths = Some(widths);
+        output
+    }
+
+    fn render_row(&mut self, row: Vec<Cell>, widths: &[usize]) -> String {
+        let mut output = String::new();
+        if self.pagesize > 0 && self.page_rows == self.pagesize {
+            let headings = self.fit_headings(widths);
+            output.push_str(&self.layout.repeated_heading(&headings, widths));
+            self.page_rows = 0;
+        }
+        self.page_rows += 1;
+        let row = row
+            .into_iter()
+            .zip(widths)
+            .map(|(cell, width)| Cell {
+                content: fit(&cell.content, *width, self.fitting.truncate.then_some("…")),
+                ..cell
+            })
+            .collect::<Vec<_>>();
+        output.push_str(&self.layout.row(&row, widths));
+        output
+    }
+}
+
+impl<L: Layout> ResultFormatter for AlignedFormatter<L> {
+    fn header(&mut self, columns: &[Column]) -> String {
+        self.headings = columns.iter().map(|x| x.name.clone()).collect();
+        self.display_sizes = columns.iter().map(|x| x.display_size).collect();
+        String::new()
+    }
+
+    fn row(&mut self, row: &[Option<Value>]) -> String {
+        let row = row.iter().map(Cell::new).collect::<Vec<_>>();
+        match self.widths.take() {
+            Some(widths) => {
+                let output = self.render_row(row, &widths);
+                self.widths = Some(widths);
+                output
+            }
+            None => {
+                self.sample.push(row);
+                if self.sample.len() < SAMPLE_ROWS {
+                    String::new()
+                } else {
+                    self.flush_sample(true)
+                }
+            }
+        }
+    }
+
+    fn footer(&mut self) -> String {
+        let mut output = String::new();
+        if self.widths.is_none() {
+            if self.sample.is_empty() {
+                return output;
+            }
+            output = self.flush_sample(false);
+        }
+        if let Some(widths) = &self.widths {
+            output.push_str(&self.layout.end(widths));
+        }
+        output
+    }
+}
+
+/// Splits cells into lines, each line with cells padded to widths.
+pub fn cell_lines(
+    cells: &[Cell],
+    widths: &[usize],
+    align_numeric: bool,
+    palette: &Palette,
+) -> Vec<Vec<String>> {
+    let cells = cells
+        .iter()
+        .map(|x| (x, x.content.lines().collect::<Vec<_>>()))
+        .collect::<Vec<_>>();
+    let height = cells
+        .iter()
+        .map(|(_, lines)| lines.len())
+        .max()
+        .unwrap_or_default()
+        .max(1);
+    (0..height)
+        .map(|y| {
+            cells
+                .iter()
+                .zip(widths)
+                .map(|((cell, lines), width)| {
+                    let content = lines.get(y).copied().unwrap_or_default();
+                    let padding =
+                        " ".repeat(width.saturating_sub(console::measure_text_width(content)));
+                    let content = if cell.null {
+                        content.italic().color(palette.null).to_string()
+                    } else if cell.heading {
+                        content.color(palette.heading).to_string()
+                    } else {
+                        content.to_string()
+                    };
+                    if align_numeric && cell.numeric {
+                        format!("{padding}{content}")
+                    } else {
+                        format!("{content}{padding}")
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
@@ -0,0 +1,76 @@
+use std::collections::BTreeMap;
+
+use yasqlplus_client::wrapper::{Column, Value};
+
+use crate::command::{ColumnSettings, Format};
+
+use super::{fit, is_numeric};
+
+/// Applies settings of `COLUMN` to columns and values before formatting.
+pub struct ColumnsFormat {
+    settings: Vec<Option<ColumnSettings>>,
+}
+
+impl ColumnsFormat {
+    pub fn new(columns: &[Column], settings: &BTreeMap<String, ColumnSettings>) -> Self {
+        Self {
+            settings: columns
+                .iter()
+                .map(|x| settings.get(&x.name.to_uppercase()).cloned())
+                .collect(),
+        }
+    }
+
+    /// Columns to print, named by headings, as wide as formats.
+    pub fn columns(&self, columns: &[Column]) -> Vec<Column> {
+        columns
+            .iter()
+            .zip(&self.settings)
+            .filter_map(|(column, settings)| {
+                let mut column = column.clone();
+                if let Some(settings) = settings {
+                    if !settings.print {
+                        return None;
+                    }
+                    if let Some(heading) = &settings.heading {
+                        column.name = heading.clone();
+                    }
+                    match &settings.format {
+                        Some(Format::Width(width)) => column.display_size = *width,
+                        Some(Format::Number(format)) => column.display_size = format.width(),
+                        None => {}
+                    }
+                }
+                Some(column)
+            })
+            .collect()
+    }
+
+    pub fn row(&self, row: Vec<Option<Value>>) -> Vec<Option<Value>> {
+        row.into_iter()
+            .zip(&self.settings)
+            .filter_map(|(value, settings)| match settings {
+                None => Some(value),
+                Some(settings) if !settings.print => None,
+                Some(settings) => Some(format_value(value, settings)),
+            })
+            .collect()
+    }
+}
+
+fn format_value(value: Option<Value>, settings: &ColumnSettings) -> Option<Value> {
+    let Some(value) = value else {
+        return settings.null.clone().map(Value::VarChar);
+    };
+    match &settings.format {
+        Some(Format::Number(format)) if is_numeric(&value) => {
+            Some(Value::Number(format.format(&value.to_string())))
+        }
+        Some(Format::Width(width)) if !is_numeric(&value) => Some(Value::VarChar(fit(
+            &value.to_string(),
+            *width,
+            (!settings.wrapped).then_some(""),
+        ))),
+        _ => Some(value),
+    }
+}
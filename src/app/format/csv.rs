@@ -0,0 +1,99 @@
+use yasqlplus_client::wrapper::{Column, Value};
+
+use super::{is_numeric, ResultFormatter};
+
+/// Delimiter separated values, CSV or TSV.
+pub struct CsvFormatter {
+    delimiter: char,
+    quote: bool,
+    escape: bool,
+}
+
+impl CsvFormatter {
+    /// Non-numeric values will be quoted if `quote` is on, others only if they have the
+    /// delimiter, quotes or newlines.
+    pub fn new(delimiter: char, quote: bool) -> Self {
+        Self {
+            delimiter,
+            quote,
+            escape: false,
+        }
+    }
+
+    /// Tab separated values, with tabs and newlines escaped.
+    pub fn tsv() -> Self {
+        Self {
+            delimiter: '\t',
+            quote: false,
+            escape: true,
+        }
+    }
+
+    fn field(&self, value: &str, quote: bool) -> String {
+        let special = |ch| ch == self.delimiter || matches!(ch, '"' | '\n' | '\r');
+        if quote || (!self.escape && value.contains(special)) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else if self.escape {
+            value
+                .replace('\\', "\\\\")
+                .replace('\t', "\\t")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r")
+        } else {
+            value.to_string()
+        }
+    }
+
+    fn line(&self, fields: impl Iterator<Item = String>) -> String {
+        let mut line = fields.collect::<Vec<_>>().join(&self.delimiter.to_string());
+        line.push('\n');
+        line
+    }
+}
+
+impl ResultFormatter for CsvFormatter {
+    fn header(&mut self, columns: &[Column]) -> String {
+        self.line(columns.iter().map(|x| self.field(&x.name, self.quote)))
+    }
+
+    fn row(&mut self, row: &[Option<Value>]) -> String {
+        self.line(row.iter().map(|value| match value {
+            Some(value) => self.field(&value.to_string(), self.quote && !is_numeric(value)),
+            None => String::new(),
+        }))
+    }
+
+    fn footer(&mut self) -> String {
+        String::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use yasqlplus_client::wrapper::Value;
+
+    use super::{CsvFormatter, ResultFormatter};
+
+    #[test]
+    fn quote() {
+        let row = [
+            Some(Value::VarChar("a;b".to_string())),
+            Some(Value::VarChar("say \"hi\"".to_string())),
+            Some(Value::VarChar("x\ny".to_string())),
+            Some(Value::VarChar("plain".to_string())),
+            None,
+        ];
+        assert_eq!(
+            CsvFormatter::new(';', false).row(&row),
+            "\"a;b\";\"say \"\"hi\"\"\";\"x\ny\";plain;\n"
+        );
+        assert_eq!(
+            CsvFormatter::new(',', true).row(&row),
+            "\"a;b\",\"say \"\"hi\"\"\",\"x\ny\",\"plain\",\n"
+        );
+        assert_eq!(
+            CsvFormatter::tsv().row(&row),
+            "a;b\tsay \"hi\"\tx\\ny\tplain\t\n"
+        );
+    }
+}
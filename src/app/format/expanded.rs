@@ -0,0 +1,150 @@
+use colored::Colorize;
+use yasqlplus_client::wrapper::{Column, Value};
+
+use crate::app::theme::Palette;
+
+use super::{Cell, ResultFormatter};
+
+/// Each row as `COLUMN : value` lines, records separated by `-[ RECORD n ]---`.
+#[derive(Default)]
+pub struct ExpandedFormatter {
+    headings: Vec<String>,
+    width: usize,
+    records: usize,
+    palette: Palette,
+}
+
+impl ExpandedFormatter {
+    pub fn new(palette: Palette) -> Self {
+        Self {
+            palette,
+            ..Default::default()
+        }
+    }
+}
+
+impl ResultFormatter for ExpandedFormatter {
+    fn header(&mut self, columns: &[Column]) -> String {
+        self.headings = columns.iter().map(|x| x.name.clone()).collect();
+        self.width = self
+            .headings
+            .iter()
+            .map(|x| console::measure_text_width(x))
+            .max()
+            .unwrap_or_default();
+        String::new()
+    }
+
+    fn row(&mut self, row: &[Option<Value>]) -> String {
+        self.records += 1;
+        let indent = " ".repeat(self.width + 3);
+        let lines = self
+            .headings
+            .iter()
+            .zip(row.iter().map(Cell::new))
+            .map(|(heading, cell)| {
+                let padding = " ".repeat(self.width - console::measure_text_width(heading));
+                let content = if cell.null {
+                    cell.content.italic().color(self.palette.null).to_string()
+                } else {
+                    // align following lines of multi-line values
+                    cell.content
+                        .lines()
+                        .collect::<Vec<_>>()
+                        .join(&format!("\n{indent}"))
+                };
+                let heading = heading.color(self.palette.heading);
+                format!("{heading}{padding} : {content}")
+            })
+            .collect::<Vec<_>>();
+
+        let separator = format!("-[ RECORD {} ]", self.records);
+        let width = lines
+            .iter()
+            .flat_map(|x| x.lines())
+            .map(console::measure_text_width)
+            .max()
+            .unwrap_or_default();
+        let dashes = "-".repeat(width.saturating_sub(separator.len()));
+        let separator = format!("{separator}{dashes}").color(self.palette.border);
+        format!("{separator}\n{}\n", lines.join("\n"))
+    }
+
+    fn footer(&mut self) -> String {
+        String::new()
+    }
+}
+
+/// Uses the expanded format once output of `formatter` is wider than `width`.
+///
+/// Decided by the first output of `formatter`, rows before that are formatted both ways.
+pub struct AutoExpandedFormatter {
+    formatter: Box<dyn ResultFormatter>,
+    expanded: ExpandedFormatter,
+    width: usize,
+    decision: Option<bool>,
+    buffer: String,
+}
+
+impl AutoExpandedFormatter {
+    pub fn new(formatter: Box<dyn ResultFormatter>, width: usize, palette: Palette) -> Self {
+        Self {
+            formatter,
+            expanded: ExpandedFormatter::new(palette),
+            width,
+            decision: None,
+            buffer: String::new(),
+        }
+    }
+
+    fn decide(&mut self, output: String) -> String {
+        if output.is_empty() {
+            return output;
+        }
+        let width = console::measure_text_width(output.lines().next().unwrap_or_default());
+        let expanded = width > self.width;
+        self.decision = Some(expanded);
+        let buffer = std::mem::take(&mut self.buffer);
+        if expanded {
+            buffer
+        } else {
+            output
+        }
+    }
+}
+
+impl ResultFormatter for AutoExpandedFormatter {
+    fn header(&mut self, columns: &[Column]) -> String {
+        self.expanded.header(columns);
+        let output = self.formatter.header(columns);
+        self.decide(output)
+    }
+
+    fn row(&mut self, row: &[Option<Value>]) -> String {
+        match self.decision {
+            Some(true) => self.expanded.row(row),
+            Some(false) => self.formatter.row(row),
+            None => {
+                let expanded = self.expanded.row(row);
+                self.buffer.push_str(&expanded);
+                let output = self.formatter.row(row);
+                self.decide(output)
+            }
+        }
+    }
+
+    fn footer(&mut self) -> String {
+        match self.decision {
+            Some(true) => self.expanded.footer(),
+            Some(false) => self.formatter.footer(),
+            None => {
+                let output = self.formatter.footer();
+                let mut output = self.decide(output);
+                if self.decision == Some(true) {
+                    output.push_str(&self.expanded.footer());
+                }
+                output
+            }
+        }
+    }
+}
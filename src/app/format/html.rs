@@ -0,0 +1,40 @@
+use yasqlplus_client::wrapper::{Column, Value};
+
+use super::ResultFormatter;
+
+/// HTML `<table>`.
+#[derive(Default)]
+pub struct HtmlFormatter;
+
+impl ResultFormatter for HtmlFormatter {
+    fn header(&mut self, columns: &[Column]) -> String {
+        let cells = columns
+            .iter()
+            .map(|x| format!("<th>{}</th>", escape(&x.name)))
+            .collect::<String>();
+        format!("<table>\n<tr>{cells}</tr>\n")
+    }
+
+    fn row(&mut self, row: &[Option<Value>]) -> String {
+        let cells = row
+            .iter()
+            .map(|value| match value {
+                Some(value) => format!("<td>{}</td>", escape(&value.to_string())),
+                None => "<td></td>".to_string(),
+            })
+            .collect::<String>();
+        format!("<tr>{cells}</tr>\n")
+    }
+
+    fn footer(&mut self) -> String {
+        "</table>\n".to_string()
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,142 @@
+use yasqlplus_client::wrapper::{Column, Value};
+
+use super::{is_numeric, ResultFormatter};
+
+/// JSON array of objects, or newline delimited JSON.
+pub struct JsonFormatter {
+    ndjson: bool,
+    columns: Vec<String>,
+    rows: usize,
+}
+
+impl JsonFormatter {
+    pub fn new(ndjson: bool) -> Self {
+        Self {
+            ndjson,
+            columns: vec![],
+            rows: 0,
+        }
+    }
+}
+
+impl ResultFormatter for JsonFormatter {
+    fn header(&mut self, columns: &[Column]) -> String {
+        self.columns = columns.iter().map(|x| json_string(&x.name)).collect();
+        if self.ndjson {
+            String::new()
+        } else {
+            "[".to_string()
+        }
+    }
+
+    fn row(&mut self, row: &[Option<Value>]) -> String {
+        let object = self
+            .columns
+            .iter()
+            .zip(row)
+            .map(|(name, value)| format!("{name}:{}", json_value(value.as_ref())))
+            .collect::<Vec<_>>()
+            .join(",");
+        self.rows += 1;
+        match (self.ndjson, self.rows) {
+            (true, _) => format!("{{{object}}}\n"),
+            (false, 1) => format!("\n  {{{object}}}"),
+            (false, _) => format!(",\n  {{{object}}}"),
+        }
+    }
+
+    fn footer(&mut self) -> String {
+        match (self.ndjson, self.rows) {
+            (true, _) => String::new(),
+            (false, 0) => "]\n".to_string(),
+            (false, _) => "\n]\n".to_string(),
+        }
+    }
+}
+
+fn json_value(value: Option<&Value>) -> String {
+    match value {
+        None => "null".to_string(),
+        Some(Value::Bool(v)) => v.to_string(),
+        Some(Value::Float(v)) if !v.is_finite() => json_string(&v.to_string()),
+        Some(Value::Double(v)) if !v.is_finite() => json_string(&v.to_string()),
+        Some(Value::Number(v)) => json_number(v).unwrap_or_else(|| json_string(v)),
+        Some(v) if is_numeric(v) => v.to_string(),
+        Some(v) => json_string(&v.to_string()),
+    }
+}
+
+/// Normalizes a number to the JSON grammar, e.g. `.5` to `0.5`, `None` if it's not a number.
+fn json_number(value: &str) -> Option<String> {
+    let all_digits = |x: &str| x.chars().all(|x| x.is_ascii_digit());
+    let (sign, value) = match value.strip_prefix('-') {
+        Some(value) => ("-", value),
+        None => ("", value.strip_prefix('+').unwrap_or(value)),
+    };
+    let (mantissa, exponent) = match value.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (value, None),
+    };
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if integer.is_empty() && fraction.is_empty() || !all_digits(integer) || !all_digits(fraction) {
+        return None;
+    }
+    let mut number = match integer.trim_start_matches('0') {
+        "" => "0",
+        integer => integer,
+    }
+    .to_string();
+    if !fraction.is_empty() {
+        number = format!("{number}.{fraction}");
+    }
+    if let Some(exponent) = exponent {
+        let digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+        if digits.is_empty() || !all_digits(digits) {
+            return None;
+        }
+        number = format!("{number}e{exponent}");
+    }
+    Some(format!("{sign}{number}"))
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for ch in value.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            ch if ch.is_control() => escaped.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use yasqlplus_client::wrapper::Value;
+
+    use super::json_value;
+
+    #[test]
+    fn numbers() {
+        let number = |x: &str| json_value(Some(&Value::Number(x.to_string())));
+        assert_eq!(number("12"), "12");
+        assert_eq!(number(".5"), "0.5");
+        assert_eq!(number("-.5"), "-0.5");
+        assert_eq!(number("1."), "1");
+        assert_eq!(number("+3"), "3");
+        assert_eq!(number("007"), "7");
+        assert_eq!(number("1.5E+10"), "1.5e+10");
+        assert_eq!(number("12345678901234567890"), "12345678901234567890");
+        assert_eq!(number("inf"), "\"inf\"");
+        assert_eq!(number("NaN"), "\"NaN\"");
+        assert_eq!(number("."), "\".\"");
+        assert_eq!(json_value(Some(&Value::Double(f64::NAN))), "\"NaN\"");
+    }
+}
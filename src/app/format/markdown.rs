@@ -0,0 +1,46 @@
+use yasqlplus_client::wrapper::{Column, Value};
+
+use super::{is_numeric, ResultFormatter};
+
+/// Markdown(GFM) table.
+#[derive(Default)]
+pub struct MarkdownFormatter;
+
+impl ResultFormatter for MarkdownFormatter {
+    fn header(&mut self, columns: &[Column]) -> String {
+        format!(
+            "{}\n|{}|\n",
+            line(columns.iter().map(|x| escape(&x.name))),
+            columns
+                .iter()
+                .map(|_| " --- ")
+                .collect::<Vec<_>>()
+                .join("|")
+        )
+    }
+
+    fn row(&mut self, row: &[Option<Value>]) -> String {
+        let cells = row.iter().map(|value| match value {
+            Some(value) if is_numeric(value) => value.to_string(),
+            Some(value) => escape(&value.to_string()),
+            None => String::new(),
+        });
+        format!("{}\n", line(cells))
+    }
+
+    fn footer(&mut self) -> String {
+        String::new()
+    }
+}
+
+fn line(cells: impl Iterator<Item = String>) -> String {
+    format!("| {} |", cells.collect::<Vec<_>>().join(" | "))
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
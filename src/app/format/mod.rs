@@ -0,0 +1,163 @@
+mod aligned;
+mod columns;
+mod csv;
+mod expanded;
+mod html;
+mod json;
+mod markdown;
+mod table;
+mod text;
+
+pub use aligned::*;
+pub use columns::*;
+pub use csv::*;
+pub use expanded::*;
+pub use html::*;
+pub use json::*;
+pub use markdown::*;
+pub use table::*;
+pub use text::*;
+
+use yasqlplus_client::wrapper::{Column, Value};
+
+use crate::command::{Expanded, ResultFormat};
+
+use super::theme::Palette;
+
+/// Formats result set row by row.
+///
+/// Formatters which need some rows(e.g. to align columns) could keep them and output later.
+pub trait ResultFormatter {
+    /// Output before rows.
+    fn header(&mut self, columns: &[Column]) -> String;
+
+    fn row(&mut self, row: &[Option<Value>]) -> String;
+
+    /// Output after all rows.
+    fn footer(&mut self) -> String;
+}
+
+/// Options to create a formatter.
+#[derive(Debug, Clone, Copy)]
+pub struct FormatOptions {
+    pub format: ResultFormat,
+    /// Heading of table/text will be repeated every `pagesize` rows.
+    pub pagesize: usize,
+    /// Table/text could be expanded, automatically if wider than the terminal.
+    pub expanded: Expanded,
+    pub fitting: Fitting,
+    /// Colors of table/text/expanded formats.
+    pub palette: Palette,
+}
+
+pub fn create_formatter(options: FormatOptions) -> Box<dyn ResultFormatter> {
+    let FormatOptions {
+        format,
+        pagesize,
+        expanded,
+        fitting,
+        palette,
+    } = options;
+    if matches!(format, ResultFormat::Table | ResultFormat::Text) {
+        match (expanded, fitting.width) {
+            (Expanded::On, _) => return Box::new(ExpandedFormatter::new(palette)),
+            (Expanded::Auto, Some(width)) => {
+                // expanded rather than shrunk
+                let formatter = create_formatter(FormatOptions {
+                    expanded: Expanded::Off,
+                    fitting: Fitting {
+                        width: None,
+                        ..fitting
+                    },
+                    ..options
+                });
+                return Box::new(AutoExpandedFormatter::new(formatter, width, palette));
+            }
+            _ => {}
+        }
+    }
+    match format {
+        ResultFormat::Table => Box::new(TableFormatter::new(
+            TableLayout { palette },
+            pagesize,
+            fitting,
+        )),
+        ResultFormat::Csv { delimiter, quote } => Box::new(CsvFormatter::new(delimiter, quote)),
+        ResultFormat::Tsv => Box::new(CsvFormatter::tsv()),
+        ResultFormat::Json => Box::new(JsonFormatter::new(false)),
+        ResultFormat::NdJson => Box::new(JsonFormatter::new(true)),
+        ResultFormat::Markdown => Box::<MarkdownFormatter>::default(),
+        ResultFormat::Html => Box::<HtmlFormatter>::default(),
+        ResultFormat::Text => Box::new(TextFormatter::new(
+            TextLayout { palette },
+            pagesize,
+            fitting,
+        )),
+    }
+}
+
+/// Fits each line of `content` in `width` columns of display, e.g. CJK characters take two.
+///
+/// Lines too wide are wrapped, or cut with the marker (like `…`) if truncating.
+fn fit(content: &str, width: usize, truncate: Option<&str>) -> String {
+    let char_width = |x: char| console::measure_text_width(x.encode_utf8(&mut [0; 4]));
+    let limit = match truncate {
+        Some(marker) => width.saturating_sub(console::measure_text_width(marker)),
+        None => width,
+    };
+    let mut lines = vec![];
+    for line in content.lines() {
+        if console::measure_text_width(line) <= width {
+            lines.push(line.to_string());
+            continue;
+        }
+        let mut current = String::new();
+        let mut current_width = 0;
+        for x in line.chars() {
+            if current_width + char_width(x) > limit && !current.is_empty() {
+                if let Some(marker) = truncate {
+                    current.push_str(marker);
+                    break;
+                }
+                lines.push(std::mem::take(&mut current));
+                current_width = 0;
+            }
+            current.push(x);
+            current_width += char_width(x);
+        }
+        lines.push(current);
+    }
+    lines.join("\n")
+}
+
+fn is_numeric(value: &Value) -> bool {
+    matches!(
+        value,
+        Value::TinyInt(_)
+            | Value::SmallInt(_)
+            | Value::Integer(_)
+            | Value::BigInt(_)
+            | Value::Float(_)
+            | Value::Double(_)
+            | Value::Number(_)
+            | Value::Bit(_)
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::fit;
+
+    #[test]
+    fn display_width() {
+        assert_eq!(fit("abcde", 2, None), "ab\ncd\ne");
+        assert_eq!(fit("abcde", 2, Some("")), "ab");
+        assert_eq!(fit("abcde", 3, Some("…")), "ab…");
+        assert_eq!(fit("ab\nc", 2, None), "ab\nc");
+        assert_eq!(fit("", 2, None), "");
+        assert_eq!(fit("中文字", 4, None), "中文\n字");
+        assert_eq!(fit("a中文", 4, None), "a中\n文");
+        assert_eq!(fit("中文字", 4, Some("")), "中文");
+        assert_eq!(fit("中文字", 4, Some("…")), "中…");
+    }
+}
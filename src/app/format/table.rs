@@ -0,0 +1,74 @@
+use colored::Colorize;
+
+use crate::app::theme::Palette;
+
+use super::{cell_lines, AlignedFormatter, Cell, Layout};
+
+/// Table with rounded border.
+pub type TableFormatter = AlignedFormatter<TableLayout>;
+
+pub struct TableLayout {
+    pub palette: Palette,
+}
+
+impl TableLayout {
+    fn border(&self, widths: &[usize], (left, middle, right): (char, char, char)) -> String {
+        let line = widths
+            .iter()
+            .map(|x| "─".repeat(x + 2))
+            .collect::<Vec<_>>()
+            .join(&middle.to_string());
+        format!(
+            "{}\n",
+            format!("{left}{line}{right}").color(self.palette.border)
+        )
+    }
+
+    fn line(&self, cells: &[String]) -> String {
+        let bar = "│".color(self.palette.border).to_string();
+        format!("{bar} {} {bar}\n", cells.join(&format!(" {bar} ")))
+    }
+
+    fn heading_line(&self, headings: &[String], widths: &[usize]) -> String {
+        let cells = headings
+            .iter()
+            .map(|x| Cell::heading(x))
+            .collect::<Vec<_>>();
+        self.row(&cells, widths)
+    }
+}
+
+impl Layout for TableLayout {
+    fn heading(&self, headings: &[String], widths: &[usize]) -> String {
+        format!(
+            "{}{}{}",
+            self.border(widths, ('╭', '┬', '╮')),
+            self.heading_line(headings, widths),
+            self.border(widths, ('├', '┼', '┤'))
+        )
+    }
+
+    fn repeated_heading(&self, headings: &[String], widths: &[usize]) -> String {
+        format!(
+            "{}{}{}",
+            self.border(widths, ('├', '┼', '┤')),
+            self.heading_line(headings, widths),
+            self.border(widths, ('├', '┼', '┤'))
+        )
+    }
+
+    fn row(&self, cells: &[Cell], widths: &[usize]) -> String {
+        cell_lines(cells, widths, false, &self.palette)
+            .iter()
+            .map(|x| self.line(x))
+            .collect()
+    }
+
+    fn end(&self, widths: &[usize]) -> String {
+        self.border(widths, ('╰', '┴', '╯'))
+    }
+
+    fn overhead(&self, columns: usize) -> usize {
+        columns * 3 + 1
+    }
+}
@@ -0,0 +1,51 @@
+use colored::Colorize;
+
+use crate::app::theme::Palette;
+
+use super::{cell_lines, AlignedFormatter, Cell, Layout};
+
+/// Plain text aligned with spaces, headings underlined with `-`.
+pub type TextFormatter = AlignedFormatter<TextLayout>;
+
+pub struct TextLayout {
+    pub palette: Palette,
+}
+
+impl TextLayout {
+    fn line(&self, cells: &[String]) -> String {
+        format!("{}\n", cells.join(" ").trim_end())
+    }
+}
+
+impl Layout for TextLayout {
+    fn heading(&self, headings: &[String], widths: &[usize]) -> String {
+        let headings = headings
+            .iter()
+            .map(|x| Cell::heading(x))
+            .collect::<Vec<_>>();
+        let underline = widths
+            .iter()
+            .map(|x| "-".repeat(*x).color(self.palette.border).to_string())
+            .collect::<Vec<_>>();
+        format!("{}{}", self.row(&headings, widths), self.line(&underline))
+    }
+
+    fn repeated_heading(&self, headings: &[String], widths: &[usize]) -> String {
+        format!("\n{}", self.heading(headings, widths))
+    }
+
+    fn row(&self, cells: &[Cell], widths: &[usize]) -> String {
+        cell_lines(cells, widths, true, &self.palette)
+            .iter()
+            .map(|x| self.line(x))
+            .collect()
+    }
+
+    fn end(&self, _widths: &[usize]) -> String {
+        String::new()
+    }
+
+    fn overhead(&self, columns: usize) -> usize {
+        columns.saturating_sub(1)
+    }
+}
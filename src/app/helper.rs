@@ -1,9 +1,13 @@
-use std::{rc::Rc, sync::RwLock};
+use std::{
+    rc::Rc,
+    sync::{Arc, Mutex, RwLock},
+};
 
-use rustyline::{hint::HistoryHinter, Completer, Helper, Highlighter, Hinter, Validator};
+use rustyline::{Completer, Helper, Highlighter, Hinter, Validator};
 
 use super::{
-    completer::YspCompleter, context::Context, highlight::YspHightligter, validate::YspValidator,
+    completer::YspCompleter, context::Context, highlight::YspHightligter, hinter::YspHinter,
+    input::Pending, validate::YspValidator,
 };
 
 #[derive(Completer, Helper, Highlighter, Hinter, Validator)]
@@ -11,7 +15,7 @@ pub struct YspHelper {
     #[rustyline(Validator)]
     validator: YspValidator,
     #[rustyline(Hinter)]
-    hinter: HistoryHinter,
+    hinter: YspHinter,
     #[rustyline(Highlighter)]
     hightligter: YspHightligter,
     #[rustyline(Completer)]
@@ -19,11 +23,11 @@ pub struct YspHelper {
 }
 
 impl YspHelper {
-    pub fn new(context: Rc<RwLock<Context>>) -> Self {
+    pub fn new(context: Rc<RwLock<Context>>, pending: Arc<Mutex<Pending>>) -> Self {
         YspHelper {
-            validator: YspValidator::new(),
-            hinter: HistoryHinter::new(),
-            hightligter: YspHightligter::new(),
+            validator: YspValidator::new(pending),
+            hinter: YspHinter::new(context.clone()),
+            hightligter: YspHightligter::new(context.clone()),
             completer: YspCompleter::new(context),
         }
     }
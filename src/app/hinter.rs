@@ -0,0 +1,336 @@
+use std::{rc::Rc, sync::RwLock};
+
+use rustyline::hint::{Hint, Hinter, HistoryHinter};
+use yasqlplus_client::{catalog::Argument, wrapper::Connection};
+
+use crate::command::{parse_command, ParseError};
+
+use super::{
+    context::Context,
+    metadata::Metadata,
+    tokenizer::{is_block, tokenize, Token, TokenKind},
+};
+
+pub struct YspHint {
+    display: String,
+    /// Whether accepting the hint inserts it, otherwise it is only shown.
+    insert: bool,
+}
+
+impl Hint for YspHint {
+    fn display(&self) -> &str {
+        &self.display
+    }
+
+    fn completion(&self) -> Option<&str> {
+        self.insert.then_some(self.display.as_str())
+    }
+}
+
+/// Hints column lists, procedure signatures and terminators, otherwise lines in history.
+pub struct YspHinter {
+    context: Rc<RwLock<Context>>,
+    history: HistoryHinter,
+}
+
+impl YspHinter {
+    pub fn new(context: Rc<RwLock<Context>>) -> Self {
+        Self {
+            context,
+            history: HistoryHinter::new(),
+        }
+    }
+
+    fn hint_subject(&self, subject: Subject) -> Option<YspHint> {
+        let context = self.context.read().unwrap();
+        let (session, connection) = (context.session()?, context.get_connection()?);
+        let metadata = &session.metadata;
+        match subject {
+            Subject::Columns {
+                schema,
+                table,
+                named,
+            } => {
+                let owner = schema.or_else(|| metadata.current_schema(connection))?;
+                let columns = metadata.columns(connection, &owner, &table);
+                if columns.is_empty() {
+                    return None;
+                }
+                let rest = columns
+                    .into_iter()
+                    .filter(|x| !named.contains(x))
+                    .collect::<Vec<_>>();
+                Some(YspHint {
+                    display: format!("{})", rest.join(", ")),
+                    insert: true,
+                })
+            }
+            Subject::Arguments(name) => {
+                let arguments = find_arguments(metadata, connection, &name)?;
+                Some(YspHint {
+                    display: signatures(&arguments)?,
+                    insert: false,
+                })
+            }
+        }
+    }
+}
+
+impl Hinter for YspHinter {
+    type Hint = YspHint;
+
+    fn hint(&self, line: &str, pos: usize, ctx: &rustyline::Context<'_>) -> Option<YspHint> {
+        if line.trim().is_empty() || pos < line.len() {
+            return None;
+        }
+        subject(line)
+            .and_then(|x| self.hint_subject(x))
+            .or_else(|| {
+                self.history.hint(line, pos, ctx).map(|display| YspHint {
+                    display,
+                    insert: true,
+                })
+            })
+            .or_else(|| {
+                terminator(line).map(|display| YspHint {
+                    display: display.to_string(),
+                    insert: true,
+                })
+            })
+    }
+}
+
+/// What the statement before the cursor could be hinted about.
+#[derive(Debug, PartialEq, Eq)]
+enum Subject {
+    /// Columns of the table not named yet, after `INSERT INTO t (` or a `,` in it.
+    Columns {
+        schema: Option<String>,
+        table: String,
+        named: Vec<String>,
+    },
+    /// Arguments of the procedure after `EXEC name(`, by parts of its name.
+    Arguments(Vec<String>),
+}
+
+fn subject(line: &str) -> Option<Subject> {
+    let tokens = tokenize(line)
+        .into_iter()
+        .filter(|x| x.kind != TokenKind::Comment)
+        .collect::<Vec<_>>();
+    let begin = tokens
+        .iter()
+        .rposition(|x| x.kind == TokenKind::Symbol(';'))
+        .map_or(0, |x| x + 1);
+    let tokens = &tokens[begin..];
+    let open = tokens
+        .iter()
+        .position(|x| x.kind == TokenKind::Symbol('('))?;
+    let (head, rest) = (&tokens[..open], &tokens[open + 1..]);
+    match head {
+        [insert, into, table @ ..] if insert.is_keyword("INSERT") && into.is_keyword("INTO") => {
+            let listing = rest.last().is_none_or(|x| x.kind == TokenKind::Symbol(','))
+                && rest.iter().all(|x| {
+                    matches!(
+                        x.kind,
+                        TokenKind::Word | TokenKind::Quoted | TokenKind::Symbol(',')
+                    )
+                });
+            if !listing {
+                return None;
+            }
+            let named = rest
+                .iter()
+                .filter(|x| x.kind != TokenKind::Symbol(','))
+                .map(|x| x.name())
+                .collect();
+            match qualified_name(table)?.as_slice() {
+                [table] => Some(Subject::Columns {
+                    schema: None,
+                    table: table.clone(),
+                    named,
+                }),
+                [schema, table] => Some(Subject::Columns {
+                    schema: Some(schema.clone()),
+                    table: table.clone(),
+                    named,
+                }),
+                _ => None,
+            }
+        }
+        [exec, name @ ..]
+            if (exec.is_keyword("EXEC") || exec.is_keyword("EXECUTE")) && rest.is_empty() =>
+        {
+            let name = qualified_name(name)?;
+            (name.len() <= 3).then_some(Subject::Arguments(name))
+        }
+        _ => None,
+    }
+}
+
+/// Parts of a name like `a.b.c`.
+fn qualified_name(tokens: &[Token]) -> Option<Vec<String>> {
+    let mut parts = vec![];
+    for (i, token) in tokens.iter().enumerate() {
+        match (i % 2, token.kind) {
+            (0, TokenKind::Word | TokenKind::Quoted) => parts.push(token.name()),
+            (1, TokenKind::Symbol('.')) => {}
+            _ => return None,
+        }
+    }
+    // not ending with `.`
+    (parts.len() * 2 == tokens.len() + 1).then_some(parts)
+}
+
+/// Arguments of `proc`, `pkg.proc`, `schema.proc` or `schema.pkg.proc`, `None` if not found.
+fn find_arguments(
+    metadata: &Metadata,
+    connection: &Connection,
+    name: &[String],
+) -> Option<Vec<Argument>> {
+    let in_package = |owner: &str, package: &str, name: &str| {
+        metadata
+            .arguments(connection, owner, package, true)
+            .into_iter()
+            .filter(|x| x.subprogram == name)
+            .collect::<Vec<_>>()
+    };
+    let arguments = match name {
+        [name] => {
+            let owner = metadata.current_schema(connection)?;
+            metadata.arguments(connection, &owner, name, false)
+        }
+        [first, name] => {
+            let arguments = match metadata.current_schema(connection) {
+                Some(owner) => in_package(&owner, first, name),
+                None => vec![],
+            };
+            if arguments.is_empty() {
+                metadata.arguments(connection, first, name, false)
+            } else {
+                arguments
+            }
+        }
+        [owner, package, name] => in_package(owner, package, name),
+        _ => vec![],
+    };
+    (!arguments.is_empty()).then_some(arguments)
+}
+
+/// Like `ID IN NUMBER, NAME OUT VARCHAR)`, overloads separated by ` | `.
+fn signatures(arguments: &[Argument]) -> Option<String> {
+    let mut signatures: Vec<(&str, Vec<String>, Option<&str>)> = vec![];
+    for argument in arguments {
+        if signatures
+            .last()
+            .is_none_or(|x| x.0 != argument.subprogram_id)
+        {
+            signatures.push((&argument.subprogram_id, vec![], None));
+        }
+        let (_, parameters, returns) = signatures.last_mut()?;
+        match (&argument.name, &argument.data_type) {
+            (None, Some(data_type)) if argument.position == 0 => *returns = Some(data_type),
+            (Some(name), data_type) => parameters.push(format!(
+                "{name} {} {}",
+                argument.in_out,
+                data_type.as_deref().unwrap_or_default()
+            )),
+            _ => {}
+        }
+    }
+    Some(
+        signatures
+            .into_iter()
+            .map(|(_, parameters, returns)| match returns {
+                Some(returns) => format!("{}) RETURN {returns}", parameters.join(", ")),
+                None => format!("{})", parameters.join(", ")),
+            })
+            .collect::<Vec<_>>()
+            .join(" | "),
+    )
+}
+
+/// What the incomplete statement needs to end, `None` for a block started on a single line.
+pub fn terminator(line: &str) -> Option<&'static str> {
+    if !matches!(parse_command(line), Err(ParseError::Incomplete(_))) {
+        return None;
+    }
+    match line.rsplit_once('\n') {
+        Some((_, last)) if last.trim().is_empty() => Some("/"),
+        Some(_) => Some("\n/"),
+        None => (!is_block(line)).then_some(";"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use yasqlplus_client::catalog::Argument;
+
+    use super::{signatures, subject, terminator, Subject};
+
+    fn argument(id: &str, name: Option<&str>, position: usize, data_type: &str) -> Argument {
+        Argument {
+            subprogram: "P".to_string(),
+            subprogram_id: id.to_string(),
+            name: name.map(str::to_string),
+            position,
+            data_type: Some(data_type.to_string()),
+            in_out: "IN".to_string(),
+        }
+    }
+
+    #[test]
+    fn subjects() {
+        assert_eq!(
+            subject("insert into hr.emp ("),
+            Some(Subject::Columns {
+                schema: Some("HR".to_string()),
+                table: "EMP".to_string(),
+                named: vec![],
+            })
+        );
+        assert_eq!(
+            subject("select 1 from dual; INSERT INTO emp(id, \"Name\", "),
+            Some(Subject::Columns {
+                schema: None,
+                table: "EMP".to_string(),
+                named: vec!["ID".to_string(), "Name".to_string()],
+            })
+        );
+        assert_eq!(subject("insert into emp (id"), None);
+        assert_eq!(subject("insert into emp values ("), None);
+        assert_eq!(
+            subject("exec pkg.proc("),
+            Some(Subject::Arguments(vec![
+                "PKG".to_string(),
+                "PROC".to_string()
+            ]))
+        );
+        assert_eq!(subject("exec proc(1"), None);
+        assert_eq!(subject("select count("), None);
+    }
+
+    #[test]
+    fn signature() {
+        let arguments = [
+            argument("1", None, 0, "NUMBER"),
+            argument("1", Some("A"), 1, "VARCHAR"),
+            argument("2", Some("A"), 1, "NUMBER"),
+            argument("2", Some("B"), 2, "DATE"),
+        ];
+        assert_eq!(
+            signatures(&arguments).as_deref(),
+            Some("A IN VARCHAR) RETURN NUMBER | A IN NUMBER, B IN DATE)")
+        );
+    }
+
+    #[test]
+    fn terminators() {
+        assert_eq!(terminator("select 1"), Some(";"));
+        assert_eq!(terminator("select 1;"), None);
+        assert_eq!(terminator("begin"), None);
+        assert_eq!(terminator("select *\nfrom t"), Some("\n/"));
+        assert_eq!(terminator("begin\n  null;\nend;\n"), Some("/"));
+        assert_eq!(terminator("desc t"), Some(";"));
+    }
+}
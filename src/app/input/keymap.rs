@@ -0,0 +1,219 @@
+use std::{
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+use rustyline::{
+    Cmd, ConditionalEventHandler, Event, EventContext, KeyCode, KeyEvent, Modifiers, Movement,
+    RepeatCount,
+};
+use thiserror::Error;
+
+use crate::app::pretty::format_statement;
+
+/// Actions keys could be bound to by `[keys]` in the config file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Executes the buffer, terminated or not.
+    ExecuteBuffer,
+    InsertNewline,
+    FormatStatement,
+    /// Edits the buffer, or the last statement if empty, in `$EDITOR`.
+    OpenEditor,
+    /// Executes the buffer once edited in `$EDITOR`.
+    EditAndExecute,
+    /// Toggles `SET EXPANDED` for the next statement.
+    ToggleExpanded,
+}
+
+/// Names of actions in the config file.
+const ACTIONS: &[(&str, Action)] = &[
+    ("execute-buffer", Action::ExecuteBuffer),
+    ("insert-newline", Action::InsertNewline),
+    ("format-statement", Action::FormatStatement),
+    ("open-editor", Action::OpenEditor),
+    ("edit-and-execute", Action::EditAndExecute),
+    ("toggle-expanded", Action::ToggleExpanded),
+];
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum KeymapError {
+    #[error("Unknown key: {0}")]
+    Key(String),
+
+    #[error("Unknown action {action}, expected one of: {expected}")]
+    Action { action: String, expected: String },
+}
+
+impl FromStr for Action {
+    type Err = KeymapError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ACTIONS
+            .iter()
+            .find(|x| x.0.eq_ignore_ascii_case(s))
+            .map(|x| x.1)
+            .ok_or_else(|| KeymapError::Action {
+                action: s.to_string(),
+                expected: ACTIONS.iter().map(|x| x.0).collect::<Vec<_>>().join(", "),
+            })
+    }
+}
+
+/// Parses a sequence of keys separated by spaces, like `ctrl-x ctrl-e`.
+pub fn parse_keys(keys: &str) -> Result<Vec<KeyEvent>, KeymapError> {
+    if keys.trim().is_empty() {
+        return Err(KeymapError::Key(keys.to_string()));
+    }
+    keys.split_whitespace().map(parse_key).collect()
+}
+
+/// Parses keys like `alt-s`, `ctrl-x`, `shift-tab` or `f5`.
+pub fn parse_key(key: &str) -> Result<KeyEvent, KeymapError> {
+    let invalid = || KeymapError::Key(key.to_string());
+    let mut modifiers = Modifiers::NONE;
+    let mut rest = key;
+    // the key itself could be `-`
+    while let Some((modifier, key)) = rest.split_once('-').filter(|x| !x.1.is_empty()) {
+        modifiers |= match modifier.to_lowercase().as_str() {
+            "ctrl" | "c" => Modifiers::CTRL,
+            "alt" | "meta" | "m" => Modifiers::ALT,
+            "shift" | "s" => Modifiers::SHIFT,
+            _ => return Err(invalid()),
+        };
+        rest = key;
+    }
+    let mut chars = rest.chars();
+    if let (Some(mut ch), None) = (chars.next(), chars.next()) {
+        // as terminals send control characters
+        if modifiers.contains(Modifiers::CTRL) {
+            ch = ch.to_ascii_uppercase();
+        }
+        return Ok(KeyEvent::new(ch, modifiers));
+    }
+    let code = match rest.to_lowercase().as_str() {
+        "enter" | "return" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        "esc" | "escape" => KeyCode::Esc,
+        "space" => KeyCode::Char(' '),
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "insert" => KeyCode::Insert,
+        "delete" => KeyCode::Delete,
+        name => match name.strip_prefix('f').map(str::parse) {
+            Some(Ok(n @ 1..=12)) => KeyCode::F(n),
+            _ => return Err(invalid()),
+        },
+    };
+    if code == KeyCode::Tab && modifiers == Modifiers::SHIFT {
+        return Ok(KeyEvent(KeyCode::BackTab, Modifiers::NONE));
+    }
+    Ok(KeyEvent(code, modifiers))
+}
+
+/// Actions done once the line is accepted, since key handlers can't reach the context.
+#[derive(Debug, Default)]
+pub struct Pending {
+    pub execute: bool,
+    pub editor: bool,
+    pub toggle_expanded: bool,
+}
+
+impl Pending {
+    /// Whether the line is accepted even if the statement is incomplete.
+    pub fn force_accept(&self) -> bool {
+        self.execute || self.editor
+    }
+}
+
+/// Does an action bound to a key.
+pub struct ActionHandler {
+    action: Action,
+    pending: Arc<Mutex<Pending>>,
+}
+
+impl ActionHandler {
+    pub fn new(action: Action, pending: Arc<Mutex<Pending>>) -> Self {
+        Self { action, pending }
+    }
+}
+
+impl ConditionalEventHandler for ActionHandler {
+    fn handle(
+        &self,
+        _evt: &Event,
+        _n: RepeatCount,
+        _positive: bool,
+        ctx: &EventContext,
+    ) -> Option<Cmd> {
+        let mut pending = self.pending.lock().unwrap();
+        match self.action {
+            Action::ExecuteBuffer => {
+                pending.execute = true;
+                Some(Cmd::AcceptLine)
+            }
+            Action::InsertNewline => Some(Cmd::Newline),
+            Action::FormatStatement => Some(Cmd::Replace(
+                Movement::WholeBuffer,
+                Some(format_statement(ctx.line())),
+            )),
+            Action::OpenEditor => {
+                pending.editor = true;
+                Some(Cmd::AcceptLine)
+            }
+            Action::EditAndExecute => {
+                pending.editor = true;
+                pending.execute = true;
+                Some(Cmd::AcceptLine)
+            }
+            Action::ToggleExpanded => {
+                pending.toggle_expanded = !pending.toggle_expanded;
+                Some(Cmd::Noop)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rustyline::{KeyCode, KeyEvent, Modifiers};
+
+    use super::{parse_key, parse_keys, Action, KeymapError};
+
+    #[test]
+    fn keys() {
+        assert_eq!(parse_key("alt-s"), Ok(KeyEvent::alt('s')));
+        assert_eq!(parse_key("ctrl-x"), Ok(KeyEvent::ctrl('X')));
+        assert_eq!(parse_key("alt--"), Ok(KeyEvent::alt('-')));
+        assert_eq!(
+            parse_key("shift-tab"),
+            Ok(KeyEvent(KeyCode::BackTab, Modifiers::NONE))
+        );
+        assert_eq!(
+            parse_key("f5"),
+            Ok(KeyEvent(KeyCode::F(5), Modifiers::NONE))
+        );
+        assert_eq!(parse_key("f13"), Err(KeymapError::Key("f13".to_string())));
+        assert_eq!(
+            parse_key("hyper-a"),
+            Err(KeymapError::Key("hyper-a".to_string()))
+        );
+        assert_eq!(
+            parse_keys("ctrl-x ctrl-e"),
+            Ok(vec![KeyEvent::ctrl('X'), KeyEvent::ctrl('E')])
+        );
+        assert!(parse_keys(" ").is_err());
+        assert_eq!("open-editor".parse(), Ok(Action::OpenEditor));
+        assert!(matches!(
+            "run".parse::<Action>(),
+            Err(KeymapError::Action { .. })
+        ));
+    }
+}
@@ -1,9 +1,11 @@
 mod error;
+mod keymap;
 mod reader;
 mod shell;
 mod single;
 
 pub use error::*;
+pub use keymap::*;
 pub use reader::*;
 pub use shell::*;
 pub use single::*;
@@ -13,4 +15,14 @@ use crate::command::Command;
 pub trait Input {
     fn get_command(&self) -> Result<Option<(Command, String)>, InputError>;
     fn line(&self, prompt: &str) -> Result<String, InputError>;
+
+    /// Text to start the next command with, ignored if not interactive.
+    fn prefill(&self, _text: &str) {}
+
+    /// Reads a line without echo from the terminal.
+    fn password(&self, prompt: &str) -> Result<String, InputError> {
+        let term = console::Term::stderr();
+        term.write_str(prompt)?;
+        Ok(term.read_secure_line()?)
+    }
 }
@@ -40,4 +40,10 @@ impl<R: Read> Input for BufReaderInput<R> {
         self.reader.borrow_mut().read_line(&mut line)?;
         Ok(line)
     }
+
+    /// Following line of the script, no terminal to hide it.
+    fn password(&self, prompt: &str) -> Result<String, InputError> {
+        self.line(prompt)
+            .map(|x| x.trim_end_matches(['\r', '\n']).to_string())
+    }
 }
@@ -1,53 +1,129 @@
-use std::{cell::RefCell, rc::Rc, sync::RwLock};
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex, RwLock},
+};
 
 use rustyline::{
-    history::FileHistory, Cmd, CompletionType, Config, EditMode, Editor, EventHandler, KeyEvent,
+    config::Configurer, history::FileHistory, Cmd, CompletionType, Config, EditMode, Editor, Event,
+    EventHandler, KeyEvent,
 };
 use rustyline::{KeyCode, Modifiers};
 
-use crate::command::{parse_command, Command, ParseError};
+use crate::command::{
+    self, parse_command, redact_command, Command, CompletionMode, Expanded, ParseError,
+};
 
-use crate::app::{context::Context, helper::YspHelper};
+use crate::app::{context::Context, editor::edit, helper::YspHelper, hinter::terminator};
 
-use super::{Input, InputError};
+use super::{Action, ActionHandler, Input, InputError, Pending};
 
 pub struct ShellInput {
     context: Rc<RwLock<Context>>,
     rl: RefCell<Editor<YspHelper, FileHistory>>,
     history_file: String,
+    pending: Arc<Mutex<Pending>>,
+    /// Text to start the next line with.
+    initial: RefCell<String>,
 }
 
 impl ShellInput {
-    pub fn new(context: Rc<RwLock<Context>>, history_file: String) -> Result<Self, InputError> {
+    /// Binds `keys` to actions after the defaults, `Alt-S` for newline and `Ctrl-X Ctrl-E` for
+    /// the editor.
+    pub fn new(
+        context: Rc<RwLock<Context>>,
+        history_file: String,
+        keys: Vec<(Vec<KeyEvent>, Action)>,
+    ) -> Result<Self, InputError> {
         let config = Config::builder()
             .history_ignore_space(true)
-            .completion_type(CompletionType::Circular)
-            .edit_mode(EditMode::Vi)
-            .auto_add_history(true)
+            // added by `get_command` without passwords
+            .auto_add_history(false)
             .history_ignore_dups(true)?
             .build();
+        let pending = Arc::new(Mutex::new(Pending::default()));
         let mut rl = Editor::with_config(config)?;
-        rl.set_helper(Some(YspHelper::new(context.clone())));
+        rl.set_helper(Some(YspHelper::new(context.clone(), pending.clone())));
         rl.bind_sequence(KeyEvent::alt('s'), EventHandler::Simple(Cmd::Newline));
         rl.bind_sequence(
             KeyEvent(KeyCode::BracketedPasteStart, Modifiers::NONE),
             Cmd::Noop,
         );
+        let defaults = [(
+            vec![KeyEvent::ctrl('X'), KeyEvent::ctrl('E')],
+            Action::OpenEditor,
+        )];
+        for (keys, action) in defaults.into_iter().chain(keys) {
+            rl.bind_sequence(
+                Event::KeySeq(keys),
+                EventHandler::Conditional(Box::new(ActionHandler::new(action, pending.clone()))),
+            );
+        }
         let _ = rl.load_history(&history_file);
         Ok(Self {
             rl: RefCell::new(rl),
             context,
             history_file,
+            pending,
+            initial: RefCell::default(),
         })
     }
+
+    /// Reads a line, editing it, or the last statement if empty, in `$EDITOR` if asked by a key.
+    /// The edited one is read again, or returned to execute.
+    fn readline(&self, rl: &mut Editor<YspHelper, FileHistory>) -> Result<String, InputError> {
+        let mut initial = std::mem::take(&mut *self.initial.borrow_mut());
+        loop {
+            let prompt = {
+                let context = self.context.read().unwrap();
+                rl.set_edit_mode(match context.edit_mode() {
+                    command::EditMode::Emacs => EditMode::Emacs,
+                    command::EditMode::Vi => EditMode::Vi,
+                });
+                rl.set_completion_type(match context.completion() {
+                    CompletionMode::Circular => CompletionType::Circular,
+                    CompletionMode::List => CompletionType::List,
+                });
+                context.get_prompt()
+            };
+            let input = rl.readline_with_initial(&prompt, (&initial, ""));
+            let pending = std::mem::take(&mut *self.pending.lock().unwrap());
+            let mut input = input?;
+            if pending.toggle_expanded {
+                let mut context = self.context.write().unwrap();
+                let expanded = match context.expanded() {
+                    Expanded::Off => Expanded::On,
+                    Expanded::On | Expanded::Auto => Expanded::Off,
+                };
+                context.set_next_expanded(Some(expanded));
+            }
+            if pending.editor {
+                if input.trim().is_empty() {
+                    if let Some(Command::SQL(sql)) = self.context.read().unwrap().get_command() {
+                        input = sql.clone();
+                    }
+                }
+                let edited = edit(&input)?.trim_end().to_string();
+                if !pending.execute {
+                    initial = edited;
+                    continue;
+                }
+                input = edited;
+            }
+            if pending.execute && matches!(parse_command(&input), Err(ParseError::Incomplete(_))) {
+                // a block in a single line
+                input.push_str(terminator(&input).unwrap_or("\n/"));
+            }
+            return Ok(input);
+        }
+    }
 }
 
 impl Input for ShellInput {
     fn get_command(&self) -> Result<Option<(Command, String)>, InputError> {
-        let input = self
-            .rl
-            .borrow_mut()
-            .readline(&self.context.read().unwrap().get_prompt())?;
+        let mut rl = self.rl.borrow_mut();
+        let input = self.readline(&mut rl)?;
+        let _ = rl.add_history_entry(redact_command(&input));
         let command = match parse_command(&input) {
             Ok(command) => Some((command, input)),
             Err(ParseError::Empty) => None,
@@ -64,6 +140,10 @@ impl Input for ShellInput {
         rl.helper_mut().unwrap().enable_validation();
         input.map_err(InputError::from)
     }
+
+    fn prefill(&self, text: &str) {
+        *self.initial.borrow_mut() = text.to_string();
+    }
 }
 
 impl Drop for ShellInput {
@@ -0,0 +1,478 @@
+/// Keywords of YashanDB SQL and PL/SQL.
+pub const KEYWORDS: &[&str] = &[
+    "ABORT",
+    "ACCESS",
+    "ACCOUNT",
+    "ACTION",
+    "ADD",
+    "ADMIN",
+    "AFTER",
+    "AGGREGATE",
+    "ALL",
+    "ALTER",
+    "ANALYZE",
+    "AND",
+    "ANY",
+    "ARCHIVELOG",
+    "ARRAY",
+    "AS",
+    "ASC",
+    "AUDIT",
+    "AUTHID",
+    "AUTOEXTEND",
+    "AUTONOMOUS_TRANSACTION",
+    "BACKUP",
+    "BEFORE",
+    "BEGIN",
+    "BETWEEN",
+    "BIGINT",
+    "BINARY",
+    "BINARY_DOUBLE",
+    "BINARY_FLOAT",
+    "BINARY_INTEGER",
+    "BIT",
+    "BLOB",
+    "BODY",
+    "BOOLEAN",
+    "BOTH",
+    "BREADTH",
+    "BULK",
+    "BY",
+    "BYTE",
+    "CACHE",
+    "CALL",
+    "CASCADE",
+    "CASE",
+    "CAST",
+    "CHAR",
+    "CHARACTER",
+    "CHECK",
+    "CHECKPOINT",
+    "CLOB",
+    "CLOSE",
+    "CLUSTER",
+    "COALESCE",
+    "COLLECT",
+    "COLUMN",
+    "COLUMNS",
+    "COMMENT",
+    "COMMIT",
+    "COMMITTED",
+    "COMPILE",
+    "COMPRESS",
+    "CONNECT",
+    "CONSTANT",
+    "CONSTRAINT",
+    "CONSTRAINTS",
+    "CONTINUE",
+    "CREATE",
+    "CROSS",
+    "CUBE",
+    "CURRENT",
+    "CURRENT_DATE",
+    "CURRENT_SCHEMA",
+    "CURRENT_TIMESTAMP",
+    "CURRENT_USER",
+    "CURSOR",
+    "CYCLE",
+    "DATABASE",
+    "DATAFILE",
+    "DATE",
+    "DAY",
+    "DBA",
+    "DEALLOCATE",
+    "DEC",
+    "DECIMAL",
+    "DECLARE",
+    "DEFAULT",
+    "DEFERRABLE",
+    "DEFERRED",
+    "DEFINER",
+    "DELETE",
+    "DEPTH",
+    "DESC",
+    "DETERMINISTIC",
+    "DIRECTORY",
+    "DISABLE",
+    "DISTINCT",
+    "DO",
+    "DOUBLE",
+    "DROP",
+    "EACH",
+    "ELSE",
+    "ELSIF",
+    "ENABLE",
+    "END",
+    "ESCAPE",
+    "EXCEPT",
+    "EXCEPTION",
+    "EXCEPTIONS",
+    "EXCLUSIVE",
+    "EXECUTE",
+    "EXISTS",
+    "EXIT",
+    "EXPLAIN",
+    "EXTERNAL",
+    "EXTRACT",
+    "FALSE",
+    "FETCH",
+    "FIRST",
+    "FLASHBACK",
+    "FLOAT",
+    "FOLLOWING",
+    "FOR",
+    "FORALL",
+    "FORCE",
+    "FOREIGN",
+    "FROM",
+    "FULL",
+    "FUNCTION",
+    "GLOBAL",
+    "GOTO",
+    "GRANT",
+    "GROUP",
+    "GROUPING",
+    "HASH",
+    "HAVING",
+    "HOUR",
+    "IDENTIFIED",
+    "IF",
+    "IMMEDIATE",
+    "IN",
+    "INCLUDING",
+    "INCREMENT",
+    "INDEX",
+    "INDEXES",
+    "INITIALLY",
+    "INNER",
+    "INOUT",
+    "INSERT",
+    "INSTEAD",
+    "INT",
+    "INTEGER",
+    "INTERSECT",
+    "INTERVAL",
+    "INTO",
+    "IS",
+    "ISOLATION",
+    "JOIN",
+    "JSON",
+    "KEEP",
+    "KEY",
+    "LAST",
+    "LEADING",
+    "LEFT",
+    "LEVEL",
+    "LIBRARY",
+    "LIKE",
+    "LIMIT",
+    "LIST",
+    "LOCAL",
+    "LOCK",
+    "LOG",
+    "LOGGING",
+    "LONG",
+    "LOOP",
+    "MATCHED",
+    "MATERIALIZED",
+    "MAXVALUE",
+    "MERGE",
+    "MINUS",
+    "MINUTE",
+    "MINVALUE",
+    "MODE",
+    "MODIFY",
+    "MONTH",
+    "NATURAL",
+    "NCHAR",
+    "NCLOB",
+    "NEXT",
+    "NO",
+    "NOAUDIT",
+    "NOCACHE",
+    "NOCOMPRESS",
+    "NOCOPY",
+    "NOCYCLE",
+    "NOLOGGING",
+    "NOMAXVALUE",
+    "NOMINVALUE",
+    "NOORDER",
+    "NOT",
+    "NOWAIT",
+    "NULL",
+    "NULLS",
+    "NUMBER",
+    "NUMERIC",
+    "NVARCHAR",
+    "NVARCHAR2",
+    "OF",
+    "OFF",
+    "OFFSET",
+    "ON",
+    "ONLINE",
+    "ONLY",
+    "OPEN",
+    "OPTION",
+    "OR",
+    "ORDER",
+    "OTHERS",
+    "OUT",
+    "OUTER",
+    "OVER",
+    "OVERWRITE",
+    "PACKAGE",
+    "PARALLEL",
+    "PARTITION",
+    "PARTITIONS",
+    "PASSWORD",
+    "PERCENT",
+    "PIVOT",
+    "PLS_INTEGER",
+    "PRAGMA",
+    "PRECEDING",
+    "PRECISION",
+    "PRIMARY",
+    "PRIOR",
+    "PRIVILEGES",
+    "PROCEDURE",
+    "PROFILE",
+    "PUBLIC",
+    "PURGE",
+    "RAISE",
+    "RANGE",
+    "RAW",
+    "READ",
+    "REAL",
+    "RECORD",
+    "RECURSIVE",
+    "RECYCLEBIN",
+    "REF",
+    "REFERENCES",
+    "REFRESH",
+    "RENAME",
+    "REPLACE",
+    "RESOURCE",
+    "RESTRICT",
+    "RETURN",
+    "RETURNING",
+    "REUSE",
+    "REVERSE",
+    "REVOKE",
+    "RIGHT",
+    "ROLE",
+    "ROLLBACK",
+    "ROLLUP",
+    "ROW",
+    "ROWID",
+    "ROWNUM",
+    "ROWS",
+    "ROWTYPE",
+    "SAVEPOINT",
+    "SCHEMA",
+    "SECOND",
+    "SEGMENT",
+    "SELECT",
+    "SEQUENCE",
+    "SERIALIZABLE",
+    "SESSION",
+    "SET",
+    "SETS",
+    "SHARE",
+    "SIBLINGS",
+    "SIZE",
+    "SMALLINT",
+    "SOME",
+    "SPACE",
+    "SQL",
+    "START",
+    "STATISTICS",
+    "STORAGE",
+    "SUBPARTITION",
+    "SYNONYM",
+    "SYSDATE",
+    "SYSTEM",
+    "SYSTIMESTAMP",
+    "TABLE",
+    "TABLESPACE",
+    "TEMPORARY",
+    "THEN",
+    "TIME",
+    "TIMESTAMP",
+    "TINYINT",
+    "TO",
+    "TRAILING",
+    "TRANSACTION",
+    "TRIGGER",
+    "TRUE",
+    "TRUNCATE",
+    "TYPE",
+    "UNBOUNDED",
+    "UNDO",
+    "UNION",
+    "UNIQUE",
+    "UNLIMITED",
+    "UNLOCK",
+    "UNPIVOT",
+    "UNTIL",
+    "UPDATE",
+    "USAGE",
+    "USER",
+    "USING",
+    "VALIDATE",
+    "VALUES",
+    "VARCHAR",
+    "VARCHAR2",
+    "VARRAY",
+    "VIEW",
+    "WAIT",
+    "WHEN",
+    "WHENEVER",
+    "WHERE",
+    "WHILE",
+    "WITH",
+    "WITHIN",
+    "WORK",
+    "WRITE",
+    "YEAR",
+    "ZONE",
+];
+
+/// Built-in functions of YashanDB.
+pub const FUNCTIONS: &[&str] = &[
+    "ABS",
+    "ACOS",
+    "ADD_MONTHS",
+    "APPROX_COUNT_DISTINCT",
+    "ARRAY_AGG",
+    "ASCII",
+    "ASIN",
+    "ATAN",
+    "ATAN2",
+    "AVG",
+    "BIN_TO_NUM",
+    "BITAND",
+    "BIT_LENGTH",
+    "CAST",
+    "CEIL",
+    "CHR",
+    "COALESCE",
+    "CONCAT",
+    "CONCAT_WS",
+    "CONVERT",
+    "CORR",
+    "COS",
+    "COSH",
+    "COUNT",
+    "COVAR_POP",
+    "COVAR_SAMP",
+    "CUME_DIST",
+    "DBTIMEZONE",
+    "DECODE",
+    "DENSE_RANK",
+    "EMPTY_BLOB",
+    "EMPTY_CLOB",
+    "EXP",
+    "EXTRACT",
+    "FIRST_VALUE",
+    "FLOOR",
+    "FROM_TZ",
+    "GREATEST",
+    "GROUPING",
+    "GROUP_CONCAT",
+    "HEXTORAW",
+    "INITCAP",
+    "INSTR",
+    "INSTRB",
+    "JSON_ARRAY",
+    "JSON_ARRAYAGG",
+    "JSON_OBJECT",
+    "JSON_OBJECTAGG",
+    "JSON_QUERY",
+    "JSON_VALUE",
+    "LAG",
+    "LAST_DAY",
+    "LAST_VALUE",
+    "LEAD",
+    "LEAST",
+    "LENGTH",
+    "LENGTHB",
+    "LISTAGG",
+    "LN",
+    "LNNVL",
+    "LOCALTIMESTAMP",
+    "LOG",
+    "LOWER",
+    "LPAD",
+    "LTRIM",
+    "MAX",
+    "MEDIAN",
+    "MIN",
+    "MOD",
+    "MONTHS_BETWEEN",
+    "NEW_TIME",
+    "NEXT_DAY",
+    "NTH_VALUE",
+    "NTILE",
+    "NULLIF",
+    "NUMTODSINTERVAL",
+    "NUMTOYMINTERVAL",
+    "NVL",
+    "NVL2",
+    "PERCENT_RANK",
+    "PERCENTILE_CONT",
+    "PERCENTILE_DISC",
+    "POWER",
+    "RANK",
+    "RATIO_TO_REPORT",
+    "RAWTOHEX",
+    "REGEXP_COUNT",
+    "REGEXP_INSTR",
+    "REGEXP_LIKE",
+    "REGEXP_REPLACE",
+    "REGEXP_SUBSTR",
+    "REMAINDER",
+    "REPLACE",
+    "REVERSE",
+    "ROUND",
+    "ROW_NUMBER",
+    "RPAD",
+    "RTRIM",
+    "SESSIONTIMEZONE",
+    "SIGN",
+    "SIN",
+    "SINH",
+    "SQRT",
+    "STDDEV",
+    "STDDEV_POP",
+    "STDDEV_SAMP",
+    "SUBSTR",
+    "SUBSTRB",
+    "SUM",
+    "SYS_CONTEXT",
+    "SYS_GUID",
+    "SYSDATE",
+    "SYSTIMESTAMP",
+    "TAN",
+    "TANH",
+    "TO_BLOB",
+    "TO_CHAR",
+    "TO_CLOB",
+    "TO_DATE",
+    "TO_DSINTERVAL",
+    "TO_MULTI_BYTE",
+    "TO_NCHAR",
+    "TO_NUMBER",
+    "TO_SINGLE_BYTE",
+    "TO_TIMESTAMP",
+    "TO_TIMESTAMP_TZ",
+    "TO_YMINTERVAL",
+    "TRANSLATE",
+    "TRIM",
+    "TRUNC",
+    "UPPER",
+    "USERENV",
+    "VARIANCE",
+    "VAR_POP",
+    "VAR_SAMP",
+    "WIDTH_BUCKET",
+];
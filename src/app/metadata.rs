@@ -0,0 +1,300 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex, OnceLock,
+    },
+    thread,
+};
+
+use yasqlplus_client::{
+    catalog::{Argument, Catalog, Object, Source, Views},
+    wrapper::{Connection, Error},
+};
+
+use super::tokenizer::{tokenize, TokenKind};
+
+/// Statements after which cached names may be out of date.
+const DDL: &[&str] = &[
+    "CREATE",
+    "ALTER",
+    "DROP",
+    "RENAME",
+    "FLASHBACK",
+    "PURGE",
+    "GRANT",
+    "REVOKE",
+];
+
+/// Names loaded by one query each.
+struct Names {
+    views: Views,
+    schemas: Vec<String>,
+    /// Tables and views by owner.
+    objects: HashMap<String, Vec<Object>>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Bumped by each refresh, so results of stale loads are dropped.
+    generation: usize,
+    names: Option<Names>,
+    current_schema: Option<String>,
+    /// Columns by owner and table, loaded on demand since there are too many to load them all.
+    /// Failed loads are kept as empty, not to query again on each key.
+    columns: HashMap<(String, String), Vec<String>>,
+    /// Arguments by owner, name and whether it is a package, loaded on demand.
+    arguments: HashMap<(String, String, bool), Vec<Argument>>,
+}
+
+#[derive(Clone, PartialEq, Eq)]
+struct Login {
+    host: String,
+    port: u16,
+    username: String,
+    password: String,
+}
+
+/// Metadata of a connection for completion, shared with the thread loading it.
+#[derive(Clone, Default)]
+pub struct Metadata {
+    state: Arc<Mutex<State>>,
+    /// Requests to the loading thread, which exits once all clones are dropped.
+    loader: Arc<OnceLock<Sender<(usize, Login)>>>,
+}
+
+impl Metadata {
+    /// Forgets columns and the current schema, and loads names again in the background with a
+    /// connection of its own. Names loaded before are kept until then.
+    ///
+    /// Loads run one at a time, refreshes during one are merged into the next.
+    pub fn refresh(&self, host: &str, port: u16, username: &str, password: &str) {
+        let generation = {
+            let mut state = self.state.lock().unwrap();
+            state.generation += 1;
+            state.current_schema = None;
+            state.columns.clear();
+            state.arguments.clear();
+            state.generation
+        };
+        let login = Login {
+            host: host.to_string(),
+            port,
+            username: username.to_string(),
+            password: password.to_string(),
+        };
+        let loader = self.loader.get_or_init(|| {
+            let (sender, receiver) = mpsc::channel();
+            let state = self.state.clone();
+            thread::spawn(move || load_names(&state, receiver));
+            sender
+        });
+        let _ = loader.send((generation, login));
+    }
+
+    pub fn schemas(&self) -> Vec<String> {
+        let state = self.state.lock().unwrap();
+        state
+            .names
+            .as_ref()
+            .map(|x| x.schemas.clone())
+            .unwrap_or_default()
+    }
+
+    /// Tables and views of the owner.
+    pub fn objects(&self, owner: &str) -> Vec<Object> {
+        let state = self.state.lock().unwrap();
+        state
+            .names
+            .as_ref()
+            .and_then(|x| x.objects.get(owner).cloned())
+            .unwrap_or_default()
+    }
+
+    /// Queried once with `connection` until refreshed.
+    pub fn current_schema(&self, connection: &impl Source) -> Option<String> {
+        let generation = {
+            let state = self.state.lock().unwrap();
+            if let Some(schema) = &state.current_schema {
+                return Some(schema.clone());
+            }
+            state.generation
+        };
+        let schema = self.catalog(connection).current_schema().ok()?;
+        self.cache(generation, |state| {
+            state.current_schema = Some(schema.clone())
+        });
+        Some(schema)
+    }
+
+    /// Queried once per table with `connection` until refreshed, even if failed.
+    pub fn columns(&self, connection: &impl Source, owner: &str, table: &str) -> Vec<String> {
+        let key = (owner.to_string(), table.to_string());
+        let generation = {
+            let state = self.state.lock().unwrap();
+            if let Some(columns) = state.columns.get(&key) {
+                return columns.clone();
+            }
+            state.generation
+        };
+        let columns = self
+            .catalog(connection)
+            .columns(owner, table)
+            .map(|x| x.into_iter().map(|x| x.name).collect::<Vec<_>>())
+            .unwrap_or_default();
+        self.cache(generation, |state| {
+            state.columns.insert(key, columns.clone());
+        });
+        columns
+    }
+
+    /// Queried once per procedure or package with `connection` until refreshed, even if failed.
+    pub fn arguments(
+        &self,
+        connection: &impl Source,
+        owner: &str,
+        name: &str,
+        package: bool,
+    ) -> Vec<Argument> {
+        let key = (owner.to_string(), name.to_string(), package);
+        let generation = {
+            let state = self.state.lock().unwrap();
+            if let Some(arguments) = state.arguments.get(&key) {
+                return arguments.clone();
+            }
+            state.generation
+        };
+        let arguments = self
+            .catalog(connection)
+            .arguments(owner, name, package)
+            .unwrap_or_default();
+        self.cache(generation, |state| {
+            state.arguments.insert(key, arguments.clone());
+        });
+        arguments
+    }
+
+    /// Keeps what was queried unless refreshed meanwhile, when it may be out of date.
+    fn cache(&self, generation: usize, keep: impl FnOnce(&mut State)) {
+        let mut state = self.state.lock().unwrap();
+        if state.generation == generation {
+            keep(&mut state);
+        }
+    }
+
+    /// Uses the views found by the load, to save probing them.
+    fn catalog<'a, S: Source>(&self, connection: &'a S) -> Catalog<'a, S> {
+        let views = self.state.lock().unwrap().names.as_ref().map(|x| x.views);
+        match views {
+            Some(views) => Catalog::with_views(connection, views),
+            None => Catalog::new(connection),
+        }
+    }
+}
+
+/// Loads names on each request with one connection, which is kept while the login is the same.
+/// Requests queued during a load are skipped but the latest.
+fn load_names(state: &Mutex<State>, requests: Receiver<(usize, Login)>) {
+    let mut connection: Option<(Login, Connection)> = None;
+    while let Ok(mut request) = requests.recv() {
+        while let Ok(latest) = requests.try_recv() {
+            request = latest;
+        }
+        let (generation, login) = request;
+        if connection.as_ref().map(|x| &x.0) != Some(&login) {
+            connection =
+                Connection::connect(&login.host, login.port, &login.username, &login.password)
+                    .ok()
+                    .map(|x| (login, x));
+        }
+        let Some((_, established)) = &connection else {
+            continue;
+        };
+        match load(established) {
+            Ok(names) => {
+                let mut state = state.lock().unwrap();
+                if state.generation == generation {
+                    state.names = Some(names);
+                }
+            }
+            // maybe lost, connect again next time
+            Err(_) => connection = None,
+        }
+    }
+}
+
+fn load(connection: &Connection) -> Result<Names, Error> {
+    let catalog = Catalog::new(connection);
+    let mut objects: HashMap<String, Vec<Object>> = HashMap::new();
+    for object in catalog.objects(None, &["TABLE", "VIEW"])? {
+        objects
+            .entry(object.owner.clone())
+            .or_default()
+            .push(object);
+    }
+    Ok(Names {
+        views: catalog.views_used(),
+        schemas: catalog.schemas()?,
+        objects,
+    })
+}
+
+/// Whether the statement may create, drop or rename objects, or change what is accessible.
+pub fn changes_metadata(sql: &str) -> bool {
+    tokenize(sql)
+        .iter()
+        .find(|x| x.kind != TokenKind::Comment)
+        .is_some_and(|x| DDL.iter().any(|keyword| x.is_keyword(keyword)))
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use yasqlplus_client::{catalog::Source, wrapper::Error};
+
+    use super::{changes_metadata, Metadata};
+
+    /// Fails every query, counting them.
+    #[derive(Default)]
+    struct Failing(Cell<usize>);
+
+    impl Source for Failing {
+        fn query(&self, _: &str, _: &[&str]) -> Result<Vec<Vec<Option<String>>>, Error> {
+            self.0.set(self.0.get() + 1);
+            Err(Error::Other)
+        }
+    }
+
+    #[test]
+    fn failed() {
+        let metadata = Metadata::default();
+        let source = Failing::default();
+        assert!(metadata.columns(&source, "APP", "ORDERS").is_empty());
+        assert!(metadata.columns(&source, "APP", "ORDERS").is_empty());
+        assert!(metadata.arguments(&source, "APP", "PKG", true).is_empty());
+        assert!(metadata.arguments(&source, "APP", "PKG", true).is_empty());
+        // the probe of views, then the query, once each
+        assert_eq!(source.0.get(), 4);
+    }
+
+    #[test]
+    fn refreshed_meanwhile() {
+        let metadata = Metadata::default();
+        metadata.state.lock().unwrap().generation += 1;
+        metadata.cache(0, |state| state.current_schema = Some("APP".to_string()));
+        assert_eq!(metadata.state.lock().unwrap().current_schema, None);
+        metadata.cache(1, |state| state.current_schema = Some("APP".to_string()));
+        assert!(metadata.state.lock().unwrap().current_schema.is_some());
+    }
+
+    #[test]
+    fn ddl() {
+        assert!(changes_metadata("create table t (a int)"));
+        assert!(changes_metadata("-- comment\nDROP VIEW v"));
+        assert!(changes_metadata("alter session set current_schema = hr"));
+        assert!(!changes_metadata("select * from t"));
+        assert!(!changes_metadata("insert into t values (1)"));
+        assert!(!changes_metadata(""));
+    }
+}
@@ -0,0 +1,132 @@
+use std::{
+    cell::RefCell,
+    fmt::Arguments,
+    fs::{File, OpenOptions},
+    io::Write,
+    process::{Child, Command, Stdio},
+};
+
+use crate::command::SpoolMode;
+
+struct SpoolFile {
+    path: String,
+    file: File,
+}
+
+struct Pager {
+    child: Child,
+    closed: bool,
+}
+
+/// Writes to stdout(or pager), and to the spool file(without ANSI colors) if spooling.
+#[derive(Default)]
+pub struct Output {
+    spool: RefCell<Option<SpoolFile>>,
+    pager: RefCell<Option<Pager>>,
+}
+
+impl Output {
+    /// So that `write!`/`writeln!` could be used.
+    pub fn write_fmt(&self, args: Arguments<'_>) {
+        let content = args.to_string();
+        match self.pager.borrow_mut().as_mut() {
+            Some(pager) => {
+                if !pager.closed {
+                    let written = pager
+                        .child
+                        .stdin
+                        .as_mut()
+                        .map(|stdin| stdin.write_all(content.as_bytes()));
+                    // quit by user
+                    pager.closed = !matches!(written, Some(Ok(_)));
+                }
+            }
+            None => print!("{content}"),
+        }
+        self.write_spool(&content);
+    }
+
+    /// Sends following output to the pager until `end_pager`.
+    pub fn start_pager(&self, mut command: Command) -> std::io::Result<()> {
+        let child = command.stdin(Stdio::piped()).spawn()?;
+        self.pager.replace(Some(Pager {
+            child,
+            closed: false,
+        }));
+        Ok(())
+    }
+
+    /// Whether the pager has been quit, no need to output more.
+    pub fn pager_closed(&self) -> bool {
+        self.pager.borrow().as_ref().is_some_and(|x| x.closed)
+    }
+
+    /// Waits for the pager to exit.
+    pub fn end_pager(&self) {
+        if let Some(mut pager) = self.pager.take() {
+            drop(pager.child.stdin.take());
+            let _ = pager.child.wait();
+        }
+    }
+
+    /// Writes to spool file only, e.g. commands typed in.
+    ///
+    /// Spooling is turned off on the first failure, which is reported once.
+    pub fn write_spool(&self, content: &str) {
+        let written = match self.spool.borrow_mut().as_mut() {
+            Some(spool) => spool
+                .file
+                .write_all(console::strip_ansi_codes(content).as_bytes()),
+            None => return,
+        };
+        if let Err(err) = written {
+            if let Some(spool) = self.spool.take() {
+                writeln!(
+                    self,
+                    "Failed to write to {}: {err}, spooling is off",
+                    spool.path
+                );
+            }
+        }
+    }
+
+    pub fn spool(&self, path: &str, mode: SpoolMode) -> std::io::Result<()> {
+        let mut options = OpenOptions::new();
+        match mode {
+            SpoolMode::Create => options.write(true).create_new(true),
+            SpoolMode::Replace => options.write(true).create(true).truncate(true),
+            SpoolMode::Append => options.append(true).create(true),
+        };
+        let file = options.open(path)?;
+        self.spool.replace(Some(SpoolFile {
+            path: path.to_string(),
+            file,
+        }));
+        Ok(())
+    }
+
+    /// Stops spooling, returns the file spooled to.
+    pub fn spool_off(&self) -> Option<String> {
+        self.spool.take().map(|x| x.path)
+    }
+
+    pub fn spooling(&self) -> Option<String> {
+        self.spool.borrow().as_ref().map(|x| x.path.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Output;
+    use crate::command::SpoolMode;
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn spool_failure() {
+        let output = Output::default();
+        output.spool("/dev/full", SpoolMode::Replace).unwrap();
+        writeln!(output, "lost");
+        assert_eq!(output.spooling(), None);
+        writeln!(output, "not spooled");
+    }
+}
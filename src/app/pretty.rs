@@ -0,0 +1,145 @@
+use crate::command::{parse_command, Command, ParseError};
+
+use super::{
+    keywords::KEYWORDS,
+    tokenizer::{is_block, tokenize, Token, TokenKind},
+};
+
+/// Keywords starting a line of their own, at the top level of a statement.
+const CLAUSES: &[&str] = &[
+    "FROM",
+    "WHERE",
+    "GROUP",
+    "HAVING",
+    "ORDER",
+    "UNION",
+    "INTERSECT",
+    "MINUS",
+    "EXCEPT",
+    "VALUES",
+    "CONNECT",
+    "START",
+    "LIMIT",
+    "JOIN",
+];
+
+/// Keywords before `JOIN`.
+const JOINS: &[&str] = &[
+    "LEFT", "RIGHT", "INNER", "FULL", "CROSS", "NATURAL", "OUTER",
+];
+
+/// Puts clauses of statements on lines of their own and keywords in upper case. PL/SQL blocks
+/// keep their lines, and commands other than SQL are returned as they are.
+pub fn format_statement(sql: &str) -> String {
+    if !matches!(
+        parse_command(sql),
+        Ok(Command::SQL(_)) | Err(ParseError::Incomplete(_))
+    ) {
+        return sql.to_string();
+    }
+    let tokens = tokenize(sql);
+    let (Some(first), Some(last)) = (tokens.first(), tokens.last()) else {
+        return sql.to_string();
+    };
+    let block = is_block(sql);
+    let mut formatted = String::new();
+    if block {
+        formatted.push_str(&sql[..first.start]);
+    }
+    // index of the first token of the current statement
+    let mut start = 0;
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate() {
+        if let Some(prev) = i.checked_sub(1).map(|x| &tokens[x]) {
+            let gap = &sql[prev.end()..token.start];
+            let separator = if block {
+                gap
+            } else if prev.kind == TokenKind::Symbol(';')
+                || (prev.kind == TokenKind::Comment && prev.text.starts_with("--"))
+                || (token.kind == TokenKind::Symbol('/') && gap.contains('\n'))
+                || (depth == 0 && i > start + 1 && starts_clause(&tokens[start..], i - start))
+            {
+                "\n"
+            } else if gap.is_empty() {
+                ""
+            } else {
+                " "
+            };
+            formatted.push_str(separator);
+        }
+        match token.kind {
+            TokenKind::Symbol('(') => depth += 1,
+            TokenKind::Symbol(')') => depth = depth.saturating_sub(1),
+            TokenKind::Symbol(';') => {
+                start = i + 1;
+                depth = 0;
+            }
+            _ => {}
+        }
+        let qualified = i > 0 && tokens[i - 1].kind == TokenKind::Symbol('.');
+        if token.kind == TokenKind::Word
+            && !qualified
+            && KEYWORDS.iter().any(|x| token.is_keyword(x))
+        {
+            formatted.push_str(&token.text.to_uppercase());
+        } else {
+            formatted.push_str(token.text);
+        }
+    }
+    if block {
+        formatted.push_str(&sql[last.end()..]);
+    }
+    formatted
+}
+
+/// Whether the `i`th token of the statement starts a clause.
+fn starts_clause(tokens: &[Token], i: usize) -> bool {
+    let is_any = |token: Option<&Token>, keywords: &[&str]| {
+        token.is_some_and(|x| keywords.iter().any(|keyword| x.is_keyword(keyword)))
+    };
+    let (prev, token, next) = (tokens.get(i - 1), tokens.get(i), tokens.get(i + 1));
+    if is_any(token, JOINS) {
+        return !is_any(prev, JOINS) && is_any(next, &["JOIN", "OUTER"]);
+    }
+    if token.is_some_and(|x| x.is_keyword("SET")) {
+        return tokens[0].is_keyword("UPDATE");
+    }
+    is_any(token, CLAUSES)
+        && !is_any(prev, JOINS)
+        // `WITHIN GROUP (ORDER BY ...)`
+        && !is_any(prev, &["WITHIN"])
+}
+
+#[cfg(test)]
+mod test {
+    use super::format_statement;
+
+    #[test]
+    fn statements() {
+        assert_eq!(
+            format_statement(
+                "select a, b.c from t left join b on t.id=b.id where a > 1 order by a;"
+            ),
+            "SELECT a, b.c\nFROM t\nLEFT JOIN b ON t.id=b.id\nWHERE a > 1\nORDER BY a;"
+        );
+        assert_eq!(
+            format_statement("delete from t where exists (select 1 from s where s.id = t.id)"),
+            "DELETE FROM t\nWHERE EXISTS (SELECT 1 FROM s WHERE s.id = t.id)"
+        );
+        assert_eq!(
+            format_statement("update t set a = 1 -- note\nwhere b = 'from x'\n/"),
+            "UPDATE t\nSET a = 1 -- note\nWHERE b = 'from x'\n/"
+        );
+        assert_eq!(
+            format_statement(
+                "select 1 from dual; select listagg(a) within group (order by a) from t"
+            ),
+            "SELECT 1\nFROM dual;\nSELECT listagg(a) WITHIN GROUP (ORDER BY a)\nFROM t"
+        );
+        assert_eq!(
+            format_statement("begin\n  if a then null; end if;\nend;\n"),
+            "BEGIN\n  IF a THEN NULL; END IF;\nEND;\n"
+        );
+        assert_eq!(format_statement("!ls from"), "!ls from");
+    }
+}
@@ -0,0 +1,254 @@
+use std::{
+    path::Path,
+    sync::{Arc, OnceLock},
+};
+
+use colored::Color;
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{self, Highlighter, ThemeSet},
+    parsing::{Scope, SyntaxDefinition, SyntaxReference, SyntaxSet},
+    util::as_24_bit_terminal_escaped,
+};
+use thiserror::Error;
+
+pub const DEFAULT_THEME: &str = "base16-ocean.dark";
+
+#[derive(Debug, Error)]
+pub enum ThemeError {
+    #[error("Unknown theme {name}, expected a .tmTheme file or one of: {bundled}")]
+    Unknown { name: String, bundled: String },
+
+    #[error("Failed to load theme {path}: {err}")]
+    Load {
+        path: String,
+        err: syntect::LoadingError,
+    },
+}
+
+/// Scope of the bundled syntax of YashanDB SQL, PL/SQL and yasqlplus commands.
+const SQL_SCOPE: &str = "source.sql.yashandb";
+
+/// Syntaxes of syntect with the bundled one, loaded once and shared.
+pub fn syntax_set() -> &'static SyntaxSet {
+    static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAX_SET.get_or_init(|| {
+        let mut builder = SyntaxSet::load_defaults_newlines().into_builder();
+        let definition = SyntaxDefinition::load_from_str(
+            include_str!("../../assets/yashandb.sublime-syntax"),
+            true,
+            None,
+        )
+        .expect("bundled syntax");
+        builder.add(definition);
+        builder.build()
+    })
+}
+
+fn sql_syntax() -> &'static SyntaxReference {
+    let syntax_set = syntax_set();
+    Scope::new(SQL_SCOPE)
+        .ok()
+        .and_then(|x| syntax_set.find_syntax_by_scope(x))
+        .unwrap_or_else(|| syntax_set.find_syntax_plain_text())
+}
+
+fn bundled_themes() -> &'static ThemeSet {
+    static THEME_SET: OnceLock<ThemeSet> = OnceLock::new();
+    THEME_SET.get_or_init(ThemeSet::load_defaults)
+}
+
+/// Whether to write colors, `false` with `NO_COLOR` or if stdout is not a terminal.
+pub fn colorize() -> bool {
+    colored::control::SHOULD_COLORIZE.should_colorize()
+}
+
+/// Colors other than highlighting, from the scopes of the theme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    pub prompt: Color,
+    pub error: Color,
+    /// Line numbers of errors and headings of results.
+    pub heading: Color,
+    /// Borders of tables.
+    pub border: Color,
+    /// `<null>` values.
+    pub null: Color,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            prompt: Color::Green,
+            error: Color::Red,
+            heading: Color::Blue,
+            border: Color::BrightBlack,
+            null: Color::BrightBlack,
+        }
+    }
+}
+
+impl Palette {
+    fn from_theme(theme: &highlighting::Theme) -> Self {
+        let highlighter = Highlighter::new(theme);
+        let default_foreground = highlighter.get_default().foreground;
+        // the first scope styled differently from plain text
+        let pick = |scopes: &[&str], fallback: Color| {
+            scopes
+                .iter()
+                .filter_map(|x| Scope::new(x).ok())
+                .map(|x| highlighter.style_for_stack(&[x]).foreground)
+                .find(|x| *x != default_foreground)
+                .map_or(fallback, true_color)
+        };
+        let default = Self::default();
+        let null = pick(&["comment"], default.null);
+        Self {
+            prompt: pick(&["string", "markup.inserted"], default.prompt),
+            error: pick(&["markup.deleted", "invalid"], default.error),
+            heading: pick(&["entity.name.function", "keyword"], default.heading),
+            border: theme
+                .settings
+                .gutter_foreground
+                .or(theme.settings.guide)
+                .map_or(null, true_color),
+            null,
+        }
+    }
+}
+
+fn true_color(color: highlighting::Color) -> Color {
+    Color::TrueColor {
+        r: color.r,
+        g: color.g,
+        b: color.b,
+    }
+}
+
+/// Highlighting theme of SQL, bundled with syntect or loaded from a `.tmTheme` file.
+#[derive(Clone)]
+pub struct Theme {
+    theme: Arc<highlighting::Theme>,
+    palette: Palette,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::load(DEFAULT_THEME).expect("bundled theme")
+    }
+}
+
+impl Theme {
+    /// Loads by name of a bundled theme, or path of a `.tmTheme` file.
+    pub fn load(name: &str) -> Result<Self, ThemeError> {
+        let theme = match bundled_themes().themes.get(name) {
+            Some(theme) => theme.clone(),
+            None if name.ends_with(".tmTheme") || Path::new(name).is_file() => {
+                ThemeSet::get_theme(name).map_err(|err| ThemeError::Load {
+                    path: name.to_string(),
+                    err,
+                })?
+            }
+            None => {
+                return Err(ThemeError::Unknown {
+                    name: name.to_string(),
+                    bundled: bundled_themes()
+                        .themes
+                        .keys()
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                })
+            }
+        };
+        Ok(Self {
+            palette: Palette::from_theme(&theme),
+            theme: Arc::new(theme),
+        })
+    }
+
+    pub fn palette(&self) -> Palette {
+        self.palette
+    }
+
+    /// Highlights a line of SQL, with the background of the theme if `background`.
+    pub fn highlight(&self, line: &str, background: bool) -> String {
+        if !colorize() {
+            return line.to_string();
+        }
+        let mut highlighter = HighlightLines::new(sql_syntax(), &self.theme);
+        match highlighter.highlight_line(line, syntax_set()) {
+            Ok(ranges) => {
+                let mut escaped = as_24_bit_terminal_escaped(&ranges, background);
+                escaped.push_str("\x1b[0m");
+                escaped
+            }
+            Err(_) => line.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use syntect::{
+        easy::ScopeRangeIterator,
+        parsing::{ParseState, ScopeStack},
+    };
+
+    use super::{sql_syntax, syntax_set, Palette, Theme, ThemeError, DEFAULT_THEME};
+
+    /// Innermost scope at the start of `token` in `line`.
+    fn scope(line: &str, token: &str) -> String {
+        let offset = line.find(token).unwrap();
+        let mut state = ParseState::new(sql_syntax());
+        let ops = state.parse_line(line, syntax_set()).unwrap();
+        let mut stack = ScopeStack::new();
+        for (range, op) in ScopeRangeIterator::new(&ops, line) {
+            stack.apply(op).unwrap();
+            if range.contains(&offset) {
+                break;
+            }
+        }
+        stack.as_slice().last().unwrap().build_string()
+    }
+
+    #[test]
+    fn load() {
+        let theme = Theme::load(DEFAULT_THEME).unwrap();
+        assert_ne!(theme.palette(), Palette::default());
+        assert!(Theme::load("Solarized (light)").is_ok());
+        assert!(matches!(
+            Theme::load("no-such-theme"),
+            Err(ThemeError::Unknown { .. })
+        ));
+        assert!(matches!(
+            Theme::load("/no/such/file.tmTheme"),
+            Err(ThemeError::Load { .. })
+        ));
+    }
+
+    #[test]
+    fn syntax() {
+        assert_eq!(sql_syntax().scope.build_string(), "source.sql.yashandb");
+        let line = "select name from v$instance where q'[it's]' = :id;\n";
+        assert_eq!(scope(line, "select"), "keyword.other.sql");
+        assert_eq!(scope(line, "v$instance"), "variable.other.sql");
+        assert_eq!(scope(line, "instance"), "variable.other.sql");
+        assert_eq!(scope(line, "s]'"), "string.quoted.other.sql");
+        assert_eq!(scope(line, " ="), "source.sql.yashandb");
+        assert_eq!(scope(line, "id"), "variable.parameter.bind.sql");
+        let line = "v_name emp.name%TYPE := NULL;\n";
+        assert_eq!(scope(line, "TYPE"), "storage.modifier.attribute.plsql");
+        assert_eq!(scope(line, ":="), "keyword.operator.sql");
+        assert_eq!(scope(line, "NULL"), "constant.language.sql");
+        assert_eq!(
+            scope("DESC hr.emp\n", "DESC"),
+            "keyword.other.command.yasqlplus"
+        );
+        assert_eq!(
+            scope("select * from t order by id desc\n", "desc"),
+            "keyword.other.sql"
+        );
+        assert_eq!(scope("  loop\n", "loop"), "keyword.control.plsql");
+    }
+}
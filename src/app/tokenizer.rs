@@ -0,0 +1,535 @@
+/// Statements ended by `/` on its own line rather than `;`.
+const BLOCKS: &[&str] = &[
+    "BEGIN",
+    "DECLARE",
+    "PROCEDURE",
+    "FUNCTION",
+    "PACKAGE",
+    "TRIGGER",
+    "TYPE",
+];
+
+/// Kinds of SQL tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// Keywords and unquoted identifiers.
+    Word,
+    /// `"identifier"`.
+    Quoted,
+    /// `'literal'`.
+    String,
+    Number,
+    Comment,
+    Symbol(char),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+    /// Byte offset in the input.
+    pub start: usize,
+}
+
+impl Token<'_> {
+    pub fn end(&self) -> usize {
+        self.start + self.text.len()
+    }
+
+    pub fn is_keyword(&self, keyword: &str) -> bool {
+        self.kind == TokenKind::Word && self.text.eq_ignore_ascii_case(keyword)
+    }
+
+    /// Name it refers to, unquoted ones in upper case.
+    pub fn name(&self) -> String {
+        match self.kind {
+            TokenKind::Quoted => self.text.trim_matches('"').to_string(),
+            _ => self.text.to_uppercase(),
+        }
+    }
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || matches!(ch, '_' | '$' | '#')
+}
+
+/// Splits SQL into tokens, whitespace skipped. Unterminated strings, quoted identifiers and
+/// comments run to the end.
+pub fn tokenize(sql: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut chars = sql.char_indices().peekable();
+    while let Some((start, ch)) = chars.next() {
+        // end before the char matching `pred`
+        let mut end_at = |pred: &dyn Fn(char) -> bool| {
+            while chars.peek().is_some_and(|&(_, ch)| !pred(ch)) {
+                chars.next();
+            }
+            chars.peek().map_or(sql.len(), |&(i, _)| i)
+        };
+        let (kind, end) = match ch {
+            _ if ch.is_whitespace() => continue,
+            '-' if sql[start + 1..].starts_with('-') => {
+                (TokenKind::Comment, end_at(&|ch| ch == '\n'))
+            }
+            '/' if sql[start + 1..].starts_with('*') => {
+                chars.next();
+                let end = sql[start + 2..]
+                    .find("*/")
+                    .map_or(sql.len(), |x| start + 2 + x + 2);
+                while chars.peek().is_some_and(|&(i, _)| i < end) {
+                    chars.next();
+                }
+                (TokenKind::Comment, end)
+            }
+            '\'' | '"' => {
+                let quote = ch;
+                let mut end = sql.len();
+                while let Some((i, ch)) = chars.next() {
+                    if ch == quote {
+                        // '' in a literal
+                        if chars.peek().is_some_and(|&(_, next)| next == quote) {
+                            chars.next();
+                            continue;
+                        }
+                        end = i + 1;
+                        break;
+                    }
+                }
+                let kind = if quote == '"' {
+                    TokenKind::Quoted
+                } else {
+                    TokenKind::String
+                };
+                (kind, end)
+            }
+            _ if ch.is_ascii_digit() => (
+                TokenKind::Number,
+                end_at(&|ch| !(ch.is_ascii_alphanumeric() || ch == '.')),
+            ),
+            _ if is_word_char(ch) => (TokenKind::Word, end_at(&|ch| !is_word_char(ch))),
+            _ => (TokenKind::Symbol(ch), start + ch.len_utf8()),
+        };
+        tokens.push(Token {
+            kind,
+            text: &sql[start..end],
+            start,
+        });
+    }
+    tokens
+}
+
+/// Whether the statement is or creates a PL/SQL block, by its first words.
+pub fn is_block(sql: &str) -> bool {
+    tokenize(sql)
+        .iter()
+        .filter(|x| x.kind == TokenKind::Word)
+        .take(5)
+        .any(|x| BLOCKS.iter().any(|keyword| x.is_keyword(keyword)))
+}
+
+/// A table referred in a statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableRef {
+    pub schema: Option<String>,
+    pub name: String,
+    pub alias: Option<String>,
+}
+
+/// What could be typed at the cursor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expect {
+    /// In a string literal or comment.
+    Nothing,
+    Keyword,
+    /// Tables and views, of the schema if qualified.
+    Table {
+        schema: Option<String>,
+    },
+    /// Columns of the tables, only the table if `qualified` by its name or alias.
+    Column {
+        tables: Vec<TableRef>,
+        qualified: bool,
+    },
+}
+
+/// The word under the cursor and what it could be.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Analysis {
+    /// Where the word starts.
+    pub start: usize,
+    /// Part of the word before the cursor.
+    pub word: String,
+    pub expect: Expect,
+}
+
+/// Words which are not aliases after a table.
+const NOT_ALIAS: &[&str] = &[
+    "AS",
+    "CONNECT",
+    "CROSS",
+    "EXCEPT",
+    "FETCH",
+    "FOR",
+    "FROM",
+    "FULL",
+    "GROUP",
+    "HAVING",
+    "INNER",
+    "INTERSECT",
+    "JOIN",
+    "LEFT",
+    "LIMIT",
+    "MINUS",
+    "NATURAL",
+    "OFFSET",
+    "ON",
+    "ORDER",
+    "OUTER",
+    "PIVOT",
+    "RETURNING",
+    "RIGHT",
+    "SELECT",
+    "SET",
+    "START",
+    "UNION",
+    "UNPIVOT",
+    "USING",
+    "VALUES",
+    "WHERE",
+    "WITH",
+];
+
+/// Clauses followed by table names.
+const TABLE_CLAUSES: &[&str] = &[
+    "FROM", "JOIN", "UPDATE", "INTO", "TABLE", "DESC", "DESCRIBE",
+];
+
+/// Clauses followed by expressions on columns.
+const COLUMN_CLAUSES: &[&str] = &[
+    "SELECT",
+    "WHERE",
+    "ON",
+    "SET",
+    "HAVING",
+    "BY",
+    "AND",
+    "OR",
+    "NOT",
+    "WHEN",
+    "THEN",
+    "ELSE",
+    "CASE",
+    "RETURNING",
+    "DISTINCT",
+];
+
+/// Tells what could be completed at `pos` of `line`.
+pub fn analyze(line: &str, pos: usize) -> Analysis {
+    let tokens = tokenize(line);
+
+    // tokens of the statement under the cursor
+    let begin = tokens
+        .iter()
+        .rposition(|x| x.kind == TokenKind::Symbol(';') && x.end() <= pos)
+        .map_or(0, |x| x + 1);
+    let end = tokens[begin..]
+        .iter()
+        .position(|x| x.kind == TokenKind::Symbol(';'))
+        .map_or(tokens.len(), |x| begin + x);
+    let statement = &tokens[begin..end];
+
+    let current = statement
+        .iter()
+        .position(|x| x.start < pos && pos <= x.end());
+    let (start, word, before) = match current.map(|x| (x, statement[x])) {
+        Some((_, token)) if matches!(token.kind, TokenKind::String | TokenKind::Comment) => {
+            return Analysis {
+                start: pos,
+                word: String::new(),
+                expect: Expect::Nothing,
+            };
+        }
+        Some((index, token)) if matches!(token.kind, TokenKind::Word | TokenKind::Quoted) => (
+            token.start,
+            line[token.start..pos].to_string(),
+            &statement[..index],
+        ),
+        _ => (
+            pos,
+            String::new(),
+            &statement[..statement.partition_point(|x| x.end() <= pos)],
+        ),
+    };
+    let before = before
+        .iter()
+        .filter(|x| x.kind != TokenKind::Comment)
+        .copied()
+        .collect::<Vec<_>>();
+
+    let (qualifier, before) = match before.as_slice() {
+        [rest @ .., qualifier, dot]
+            if dot.kind == TokenKind::Symbol('.')
+                && matches!(qualifier.kind, TokenKind::Word | TokenKind::Quoted) =>
+        {
+            (Some(qualifier.name()), rest)
+        }
+        _ => (None, before.as_slice()),
+    };
+
+    let clause = before.iter().rposition(|x| {
+        TABLE_CLAUSES
+            .iter()
+            .chain(COLUMN_CLAUSES)
+            .any(|keyword| x.is_keyword(keyword))
+    });
+    let expect = match clause.map(|x| (x, before[x])) {
+        Some((index, clause)) if TABLE_CLAUSES.iter().any(|x| clause.is_keyword(x)) => {
+            let previous = before.last().copied();
+            let in_list = previous
+                .is_some_and(|x| x.kind == TokenKind::Symbol(',') && clause.is_keyword("FROM"));
+            let in_columns = clause.is_keyword("INTO")
+                && before[index..]
+                    .iter()
+                    .any(|x| x.kind == TokenKind::Symbol('('));
+            if in_columns {
+                Expect::Column {
+                    tables: table_refs(statement),
+                    qualified: false,
+                }
+            } else if qualifier.is_some() || index + 1 == before.len() || in_list {
+                Expect::Table { schema: qualifier }
+            } else {
+                Expect::Keyword
+            }
+        }
+        Some(_) => {
+            let tables = table_refs(statement);
+            match qualifier {
+                Some(qualifier) => {
+                    let tables = tables
+                        .into_iter()
+                        .filter(|x| x.alias.as_ref().unwrap_or(&x.name) == &qualifier)
+                        .collect::<Vec<_>>();
+                    if tables.is_empty() {
+                        Expect::Table {
+                            schema: Some(qualifier),
+                        }
+                    } else {
+                        Expect::Column {
+                            tables,
+                            qualified: true,
+                        }
+                    }
+                }
+                None => Expect::Column {
+                    tables,
+                    qualified: false,
+                },
+            }
+        }
+        None => match qualifier {
+            Some(qualifier) => Expect::Table {
+                schema: Some(qualifier),
+            },
+            None => Expect::Keyword,
+        },
+    };
+    Analysis {
+        start,
+        word,
+        expect,
+    }
+}
+
+/// Tables after `FROM`, `JOIN`, `UPDATE` and `INTO` in the statement.
+pub fn table_refs(tokens: &[Token]) -> Vec<TableRef> {
+    let tokens = tokens
+        .iter()
+        .filter(|x| x.kind != TokenKind::Comment)
+        .collect::<Vec<_>>();
+    let is_name = |x: Option<&&Token>| {
+        x.is_some_and(|x| {
+            x.kind == TokenKind::Quoted
+                || (x.kind == TokenKind::Word && !NOT_ALIAS.iter().any(|k| x.is_keyword(k)))
+        })
+    };
+    let mut tables = vec![];
+    let mut i = 0;
+    while i < tokens.len() {
+        let clause = tokens[i];
+        i += 1;
+        if !["FROM", "JOIN", "UPDATE", "INTO"]
+            .iter()
+            .any(|x| clause.is_keyword(x))
+        {
+            continue;
+        }
+        loop {
+            if tokens
+                .get(i)
+                .is_some_and(|x| x.kind == TokenKind::Symbol('('))
+            {
+                // subqueries are not tables, but tables inside are still found
+                break;
+            }
+            if !is_name(tokens.get(i)) {
+                break;
+            }
+            let mut schema = None;
+            let mut name = tokens[i].name();
+            i += 1;
+            if tokens
+                .get(i)
+                .is_some_and(|x| x.kind == TokenKind::Symbol('.'))
+                && is_name(tokens.get(i + 1))
+            {
+                schema = Some(name);
+                name = tokens[i + 1].name();
+                i += 2;
+            }
+            if tokens.get(i).is_some_and(|x| x.is_keyword("AS")) {
+                i += 1;
+            }
+            let alias = if is_name(tokens.get(i)) {
+                i += 1;
+                Some(tokens[i - 1].name())
+            } else {
+                None
+            };
+            tables.push(TableRef {
+                schema,
+                name,
+                alias,
+            });
+            if clause.is_keyword("FROM")
+                && tokens
+                    .get(i)
+                    .is_some_and(|x| x.kind == TokenKind::Symbol(','))
+            {
+                i += 1;
+            } else {
+                break;
+            }
+        }
+    }
+    tables
+}
+
+#[cfg(test)]
+mod test {
+    use super::{analyze, tokenize, Expect, TableRef, TokenKind};
+
+    fn table(schema: Option<&str>, name: &str, alias: Option<&str>) -> TableRef {
+        TableRef {
+            schema: schema.map(str::to_string),
+            name: name.to_string(),
+            alias: alias.map(str::to_string),
+        }
+    }
+
+    fn expect(line: &str) -> Expect {
+        analyze(line, line.len()).expect
+    }
+
+    #[test]
+    fn tokens() {
+        let kinds = tokenize("select 'it''s', \"A b\" from t -- c\n/* x */ where a>=1.5;")
+            .into_iter()
+            .map(|x| (x.kind, x.text))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                (TokenKind::Word, "select"),
+                (TokenKind::String, "'it''s'"),
+                (TokenKind::Symbol(','), ","),
+                (TokenKind::Quoted, "\"A b\""),
+                (TokenKind::Word, "from"),
+                (TokenKind::Word, "t"),
+                (TokenKind::Comment, "-- c"),
+                (TokenKind::Comment, "/* x */"),
+                (TokenKind::Word, "where"),
+                (TokenKind::Word, "a"),
+                (TokenKind::Symbol('>'), ">"),
+                (TokenKind::Symbol('='), "="),
+                (TokenKind::Number, "1.5"),
+                (TokenKind::Symbol(';'), ";"),
+            ]
+        );
+    }
+
+    #[test]
+    fn tables() {
+        assert_eq!(expect("select * from "), Expect::Table { schema: None });
+        assert_eq!(expect("select * from a, "), Expect::Table { schema: None });
+        assert_eq!(
+            expect("SELECT * FROM a JOIN b"),
+            Expect::Table { schema: None }
+        );
+        assert_eq!(expect("update "), Expect::Table { schema: None });
+        assert_eq!(expect("insert into "), Expect::Table { schema: None });
+        assert_eq!(
+            expect("select * from sales."),
+            Expect::Table {
+                schema: Some("SALES".to_string())
+            }
+        );
+        assert_eq!(expect("select * from a "), Expect::Keyword);
+
+        let analysis = analyze("select * from ord", 17);
+        assert_eq!((analysis.start, analysis.word.as_str()), (14, "ord"));
+    }
+
+    #[test]
+    fn columns() {
+        let tables = vec![
+            table(None, "ORDERS", Some("O")),
+            table(Some("SALES"), "CUSTOMERS", Some("C")),
+        ];
+        let sql = "select  from orders o join sales.customers as c on o.cid = c.id";
+        assert_eq!(
+            analyze(sql, 7).expect,
+            Expect::Column {
+                tables: tables.clone(),
+                qualified: false
+            }
+        );
+        assert_eq!(
+            expect("select * from orders o join sales.customers as c on c."),
+            Expect::Column {
+                tables: vec![tables[1].clone()],
+                qualified: true
+            }
+        );
+        assert_eq!(
+            expect("update t set "),
+            Expect::Column {
+                tables: vec![table(None, "T", None)],
+                qualified: false
+            }
+        );
+        assert_eq!(
+            expect("insert into t ("),
+            Expect::Column {
+                tables: vec![table(None, "T", None)],
+                qualified: false
+            }
+        );
+        // the statement under the cursor only
+        assert_eq!(
+            expect("select * from a; select * from b where "),
+            Expect::Column {
+                tables: vec![table(None, "B", None)],
+                qualified: false
+            }
+        );
+    }
+
+    #[test]
+    fn others() {
+        assert_eq!(expect(""), Expect::Keyword);
+        assert_eq!(expect("sel"), Expect::Keyword);
+        assert_eq!(expect("select 'abc"), Expect::Nothing);
+        assert_eq!(expect("select 1 -- from "), Expect::Nothing);
+    }
+}
@@ -1,9 +1,15 @@
+use std::sync::{Arc, Mutex};
+
 use rustyline::validate::{ValidationContext, ValidationResult, Validator};
 
 use crate::command::parse_command;
 
+use super::input::Pending;
+
 pub struct YspValidator {
     pub enabled: bool,
+    /// Accepts incomplete statements for actions of keys.
+    pending: Arc<Mutex<Pending>>,
 }
 
 impl Validator for YspValidator {
@@ -11,7 +17,7 @@ impl Validator for YspValidator {
         let input = ctx.input();
 
         // validate sql mainly
-        if self.enabled {
+        if self.enabled && !self.pending.lock().unwrap().force_accept() {
             return match parse_command(input) {
                 Err(crate::command::ParseError::Incomplete(_)) => Ok(ValidationResult::Incomplete),
                 _ => Ok(ValidationResult::Valid(None)),
@@ -26,7 +32,10 @@ impl Validator for YspValidator {
 }
 
 impl YspValidator {
-    pub fn new() -> Self {
-        YspValidator { enabled: true }
+    pub fn new(pending: Arc<Mutex<Pending>>) -> Self {
+        YspValidator {
+            enabled: true,
+            pending,
+        }
     }
 }
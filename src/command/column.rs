@@ -0,0 +1,367 @@
+use std::fmt::Display;
+
+use thiserror::Error;
+
+/// Format of a column, `A20` or `999,999.99`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Format {
+    /// Width of character values.
+    Width(usize),
+    Number(NumberFormat),
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Width(width) => write!(f, "A{width}"),
+            Format::Number(format) => format.pattern.fmt(f),
+        }
+    }
+}
+
+/// Number format made of `9`, `0`, `,`, `.` and a leading `$`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberFormat {
+    pattern: String,
+}
+
+impl NumberFormat {
+    pub fn parse(pattern: &str) -> Option<Self> {
+        let digits = pattern.strip_prefix('$').unwrap_or(pattern);
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        let valid = !digits.is_empty()
+            && integer.chars().all(|x| matches!(x, '9' | '0' | ','))
+            && fraction.chars().all(|x| matches!(x, '9' | '0'))
+            && !integer.starts_with(',')
+            && !integer.ends_with(',');
+        valid.then(|| Self {
+            pattern: pattern.to_string(),
+        })
+    }
+
+    /// Width of formatted numbers, including the sign.
+    pub fn width(&self) -> usize {
+        self.pattern.len() + 1
+    }
+
+    /// Formats a decimal number, `#`s if it doesn't fit or isn't a number.
+    pub fn format(&self, number: &str) -> String {
+        let overflow = "#".repeat(self.width());
+        let currency = self.pattern.starts_with('$');
+        let pattern = self.pattern.trim_start_matches('$');
+        let (int_pattern, frac_pattern) = pattern.split_once('.').unwrap_or((pattern, ""));
+        let scale = frac_pattern.len();
+
+        let Some((negative, mut digits)) = parse_decimal(number, scale) else {
+            return overflow;
+        };
+        let fraction = digits.split_off(digits.len() - scale);
+        let integer = digits.trim_start_matches('0');
+
+        let slots = int_pattern.chars().filter(|x| *x != ',').count();
+        if integer.len() > slots {
+            return overflow;
+        }
+        // leading zeros from the first `0` in pattern
+        let zeros = int_pattern
+            .chars()
+            .filter(|x| *x != ',')
+            .position(|x| x == '0')
+            .map_or(0, |x| slots - x);
+        let integer = format!("{integer:0>zeros$}");
+
+        let mut integer_digits = integer.chars().rev().peekable();
+        let mut formatted = vec![];
+        for x in int_pattern.chars().rev() {
+            if integer_digits.peek().is_none() {
+                break;
+            }
+            match x {
+                ',' => formatted.push(','),
+                _ => formatted.extend(integer_digits.next()),
+            }
+        }
+        let mut formatted = formatted.into_iter().rev().collect::<String>();
+        if scale > 0 {
+            formatted.push('.');
+            formatted.push_str(&fraction);
+        } else if formatted.is_empty() {
+            formatted.push('0');
+        }
+        if currency {
+            formatted.insert(0, '$');
+        }
+        if negative && formatted.contains(|x: char| matches!(x, '1'..='9')) {
+            formatted.insert(0, '-');
+        }
+        format!("{formatted:>width$}", width = self.width())
+    }
+}
+
+/// Parses into sign and digits rounded to `scale` digits after the point(which is removed).
+fn parse_decimal(number: &str, scale: usize) -> Option<(bool, String)> {
+    let number = number.trim();
+    let number = if number.contains(['e', 'E']) {
+        number.parse::<f64>().ok()?.to_string()
+    } else {
+        number.to_string()
+    };
+    let (negative, number) = match number.strip_prefix('-') {
+        Some(number) => (true, number),
+        None => (false, number.strip_prefix('+').unwrap_or(&number)),
+    };
+    let (integer, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if integer.is_empty() && fraction.is_empty()
+        || !integer
+            .chars()
+            .chain(fraction.chars())
+            .all(|x| x.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let mut digits = format!("0{integer}{fraction:0<scale$}").into_bytes();
+    let round_up = fraction.as_bytes().get(scale).is_some_and(|x| *x >= b'5');
+    digits.truncate(1 + integer.len() + scale);
+    if round_up {
+        for x in digits.iter_mut().rev() {
+            if *x == b'9' {
+                *x = b'0';
+            } else {
+                *x += 1;
+                break;
+            }
+        }
+    }
+    String::from_utf8(digits).ok().map(|x| (negative, x))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ColumnOption {
+    Format(Format),
+    Heading(String),
+    /// Wraps values wider than the format if `true`, otherwise truncates them.
+    Wrapped(bool),
+    Print(bool),
+    /// Text displayed for null values.
+    Null(String),
+}
+
+/// Settings of a column accumulated from `COLUMN` commands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnSettings {
+    pub format: Option<Format>,
+    pub heading: Option<String>,
+    pub wrapped: bool,
+    pub print: bool,
+    pub null: Option<String>,
+}
+
+impl Default for ColumnSettings {
+    fn default() -> Self {
+        Self {
+            format: None,
+            heading: None,
+            wrapped: true,
+            print: true,
+            null: None,
+        }
+    }
+}
+
+impl ColumnSettings {
+    pub fn apply(&mut self, option: ColumnOption) {
+        match option {
+            ColumnOption::Format(format) => self.format = Some(format),
+            ColumnOption::Heading(heading) => self.heading = Some(heading),
+            ColumnOption::Wrapped(wrapped) => self.wrapped = wrapped,
+            ColumnOption::Print(print) => self.print = print,
+            ColumnOption::Null(null) => self.null = Some(null),
+        }
+    }
+}
+
+impl Display for ColumnSettings {
+    /// Options one per line, like listed by `COLUMN name`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(heading) = &self.heading {
+            writeln!(f, "HEADING  '{heading}'")?;
+        }
+        if let Some(format) = &self.format {
+            writeln!(f, "FORMAT   {format}")?;
+        }
+        if let Some(null) = &self.null {
+            writeln!(f, "NULL     '{null}'")?;
+        }
+        if !self.print {
+            writeln!(f, "NOPRINT")?;
+        }
+        writeln!(f, "{}", if self.wrapped { "WRAPPED" } else { "TRUNCATED" })
+    }
+}
+
+pub enum ColumnCommand {
+    /// Lists settings of all columns or the specified one.
+    List(Option<String>),
+    Clear(String),
+    Define(String, Vec<ColumnOption>),
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ColumnParsingError {
+    #[error("Unknown option: {0}")]
+    Option(String),
+
+    #[error("Missing value for {0}")]
+    MissingValue(String),
+
+    #[error("Invalid value for {option}: {value}")]
+    Value { option: String, value: String },
+}
+
+/// Splits by whitespace, except in quotes which are removed.
+fn split_tokens(command: &str) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut chars = command.chars().peekable();
+    while let Some(x) = chars.next() {
+        match x {
+            x if x.is_whitespace() => {}
+            '\'' | '"' => tokens.push(chars.by_ref().take_while(|y| *y != x).collect()),
+            x => {
+                let mut token = x.to_string();
+                while let Some(y) = chars.next_if(|y| !y.is_whitespace()) {
+                    token.push(y);
+                }
+                tokens.push(token);
+            }
+        }
+    }
+    tokens
+}
+
+/// Parses `COLUMN [name [CLEAR | option ...]]`.
+pub fn parse_column(column: &str) -> Result<ColumnCommand, ColumnParsingError> {
+    let tokens = split_tokens(column);
+    let Some((name, options)) = tokens.split_first() else {
+        return Ok(ColumnCommand::List(None));
+    };
+    let name = name.to_uppercase();
+    match options {
+        [] => return Ok(ColumnCommand::List(Some(name))),
+        [clear] if matches!(clear.to_uppercase().as_str(), "CLEAR" | "CLE") => {
+            return Ok(ColumnCommand::Clear(name))
+        }
+        _ => {}
+    }
+
+    let mut parsed = vec![];
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        let upper = option.to_uppercase();
+        let mut value = || {
+            options
+                .next()
+                .ok_or_else(|| ColumnParsingError::MissingValue(upper.clone()))
+        };
+        parsed.push(match upper.as_str() {
+            "FORMAT" | "FOR" => {
+                let value = value()?;
+                let width = value
+                    .strip_prefix(['a', 'A'])
+                    .and_then(|x| x.parse().ok())
+                    .filter(|x| *x > 0);
+                match width {
+                    Some(width) => ColumnOption::Format(Format::Width(width)),
+                    None => ColumnOption::Format(Format::Number(
+                        NumberFormat::parse(value).ok_or_else(|| ColumnParsingError::Value {
+                            option: "FORMAT".to_string(),
+                            value: value.clone(),
+                        })?,
+                    )),
+                }
+            }
+            "HEADING" | "HEA" => ColumnOption::Heading(value()?.clone()),
+            "NULL" | "NUL" => ColumnOption::Null(value()?.clone()),
+            "WRAPPED" | "WRA" => ColumnOption::Wrapped(true),
+            "TRUNCATED" | "TRU" => ColumnOption::Wrapped(false),
+            "PRINT" | "PRI" => ColumnOption::Print(true),
+            "NOPRINT" | "NOPRI" => ColumnOption::Print(false),
+            _ => return Err(ColumnParsingError::Option(option.clone())),
+        });
+    }
+    Ok(ColumnCommand::Define(name, parsed))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        parse_column, ColumnCommand, ColumnOption, ColumnParsingError, Format, NumberFormat,
+    };
+
+    #[test]
+    fn parse() {
+        assert!(matches!(parse_column(""), Ok(ColumnCommand::List(None))));
+        assert!(matches!(
+            parse_column("ename"),
+            Ok(ColumnCommand::List(Some(name))) if name == "ENAME"
+        ));
+        assert!(matches!(
+            parse_column("ename clear"),
+            Ok(ColumnCommand::Clear(name)) if name == "ENAME"
+        ));
+        assert!(matches!(
+            parse_column("ename format a20 heading 'Employee Name' tru noprint null \"-\""),
+            Ok(ColumnCommand::Define(name, options)) if name == "ENAME" && options == vec![
+                ColumnOption::Format(Format::Width(20)),
+                ColumnOption::Heading("Employee Name".to_string()),
+                ColumnOption::Wrapped(false),
+                ColumnOption::Print(false),
+                ColumnOption::Null("-".to_string()),
+            ]
+        ));
+        assert!(matches!(
+            parse_column("sal for $999,999.99"),
+            Ok(ColumnCommand::Define(_, options))
+                if options == vec![ColumnOption::Format(Format::Number(
+                    NumberFormat::parse("$999,999.99").unwrap()
+                ))]
+        ));
+
+        assert!(matches!(
+            parse_column("sal format 9x9"),
+            Err(ColumnParsingError::Value { .. })
+        ));
+        assert!(matches!(
+            parse_column("sal heading"),
+            Err(ColumnParsingError::MissingValue(_))
+        ));
+        assert!(matches!(
+            parse_column("sal justify left"),
+            Err(ColumnParsingError::Option(_))
+        ));
+    }
+
+    #[test]
+    fn number_format() {
+        let format = |pattern: &str, number: &str| {
+            NumberFormat::parse(pattern)
+                .unwrap()
+                .format(number)
+                .trim_start()
+                .to_string()
+        };
+        assert_eq!(format("999,999.99", "1234.5"), "1,234.50");
+        assert_eq!(format("999,999.99", "-1234.567"), "-1,234.57");
+        assert_eq!(format("999.99", "0.5"), ".50");
+        assert_eq!(format("990.99", "0.5"), "0.50");
+        assert_eq!(format("0999", "12"), "0012");
+        assert_eq!(format("999", "0"), "0");
+        assert_eq!(format("999", "999.5"), "####");
+        assert_eq!(format("99.9", "9.96"), "10.0");
+        assert_eq!(format("$9,999", "1234"), "$1,234");
+        assert_eq!(format("9.9", "-0.01"), ".0");
+        assert_eq!(format("9999", "1.5e2"), "150");
+        assert_eq!(format("999", "abc"), "####");
+        assert_eq!(NumberFormat::parse("999").unwrap().format("1"), "   1");
+    }
+}
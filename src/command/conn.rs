@@ -1,8 +1,9 @@
 use std::num::ParseIntError;
 
 use thiserror::Error;
+use yasqlplus_client::wrapper::ConnectInfo;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct Connection {
     pub host: Option<String>,
     pub port: Option<u16>,
@@ -19,6 +20,17 @@ impl Connection {
     }
 }
 
+impl From<ConnectInfo> for Connection {
+    fn from(value: ConnectInfo) -> Self {
+        Self {
+            host: value.host,
+            port: value.port,
+            username: value.username,
+            password: value.password,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum State {
     Username,
@@ -37,6 +49,31 @@ pub enum ConnParsingError {
 
     #[error("Invalid format.")]
     Invalid,
+
+    #[error("Expected session name after -n")]
+    Name,
+}
+
+/// Parses `[-n session] connection_string`.
+pub fn parse_conn(conn: &str) -> Result<(Option<String>, Connection), ConnParsingError> {
+    let conn = conn.trim();
+    match conn
+        .strip_prefix("-n")
+        .filter(|x| x.is_empty() || x.starts_with(char::is_whitespace))
+    {
+        Some(rest) => {
+            let rest = rest.trim_start();
+            let (name, conn) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            if name.is_empty() {
+                return Err(ConnParsingError::Name);
+            }
+            Ok((
+                Some(name.to_string()),
+                parse_connection_string(conn.trim())?,
+            ))
+        }
+        None => Ok((None, parse_connection_string(conn)?)),
+    }
 }
 
 pub fn parse_connection_string(conn: &str) -> Result<Connection, ConnParsingError> {
@@ -1,8 +1,20 @@
+mod column;
 mod conn;
 mod parser;
+mod session;
+mod set;
+mod timing;
+mod variable;
+mod whenever;
 
+pub use column::*;
 pub use conn::*;
 pub use parser::*;
+pub use session::*;
+pub use set::*;
+pub use timing::*;
+pub use variable::*;
+pub use whenever::*;
 
 #[allow(clippy::upper_case_acronyms)]
 pub enum Command {
@@ -18,8 +30,56 @@ pub enum Command {
 
 pub enum InternalCommand {
     Describe(String),
-    Connect(Connection),
+    /// To connect in the named session, the current one if not named.
+    Connect(Option<String>, Connection),
+    /// To close the connection of the current session, which could be reconnected.
+    Disconnect,
+    /// To connect the current session again with the same parameters.
+    Reconnect,
+    /// To load names for completion again.
+    Refresh,
     Exit,
+
+    /// Lists all bind variables or the specified one.
+    Variable(Option<String>),
+    DefineVariable(Variable),
+    Print(Option<String>),
+    /// To execute a single PL/SQL statement, e.g. `exec proc(:x)`.
+    Execute(String),
+
+    /// To decide what to do when an error occurs.
+    Whenever(ErrorCondition, ErrorAction),
+
+    /// To write output into a file as well.
+    Spool(SpoolCommand),
+
+    /// To change an option, e.g. `set markup csv`.
+    Set(Setting),
+
+    /// To format a column, e.g. `column sal format 999,999.99`.
+    Column(ColumnCommand),
+
+    /// Named stopwatches, e.g. `timing start load`.
+    Timing(TimingCommand),
+
+    /// To manage sessions opened by `conn -n`, e.g. `session use dev`.
+    Session(SessionCommand),
+
+    /// To edit the file in `$EDITOR`, or the last statement if not given.
+    Edit(Option<String>),
+}
+
+pub enum SpoolCommand {
+    Status,
+    Off,
+    To(String, SpoolMode),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpoolMode {
+    Create,
+    Replace,
+    Append,
 }
 
 impl Command {
@@ -29,8 +89,22 @@ impl Command {
             Command::Shell(_) => false,
             Command::Internal(internal) => match internal {
                 InternalCommand::Describe(_) => true,
-                InternalCommand::Connect(_) => false,
+                InternalCommand::Connect(..) => false,
+                InternalCommand::Disconnect => false,
+                InternalCommand::Reconnect => false,
+                InternalCommand::Refresh => true,
                 InternalCommand::Exit => false,
+                InternalCommand::Variable(_) => false,
+                InternalCommand::DefineVariable(_) => false,
+                InternalCommand::Print(_) => false,
+                InternalCommand::Execute(_) => true,
+                InternalCommand::Whenever(_, _) => false,
+                InternalCommand::Spool(_) => false,
+                InternalCommand::Set(_) => false,
+                InternalCommand::Column(_) => false,
+                InternalCommand::Timing(_) => false,
+                InternalCommand::Session(_) => false,
+                InternalCommand::Edit(_) => false,
             },
         }
     }
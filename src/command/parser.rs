@@ -1,6 +1,13 @@
+use std::borrow::Cow;
+
 use thiserror::Error;
 
-use super::{parse_connection_string, Command, ConnParsingError, InternalCommand};
+use super::{
+    parse_column, parse_conn, parse_session, parse_set, parse_timing, parse_variable_definition,
+    parse_whenever, ColumnParsingError, Command, ConnParsingError, InternalCommand,
+    SessionParsingError, SetParsingError, SpoolCommand, SpoolMode, TimingParsingError,
+    VariableParsingError, WheneverParsingError,
+};
 
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum ParseError {
@@ -18,8 +25,143 @@ pub enum ParseError {
         err: ConnParsingError,
         command: String,
     },
+
+    // Variable definition error.
+    #[error("Failed to define variable:\n{command}. \n{err}")]
+    VariableError {
+        err: VariableParsingError,
+        command: String,
+    },
+
+    // SET error.
+    #[error("Failed to parse SET:\n{command}. \n{err}")]
+    SetError {
+        err: SetParsingError,
+        command: String,
+    },
+
+    // WHENEVER error.
+    #[error("Failed to parse WHENEVER:\n{command}. \n{err}")]
+    WheneverError {
+        err: WheneverParsingError,
+        command: String,
+    },
+
+    // COLUMN error.
+    #[error("Failed to parse COLUMN:\n{command}. \n{err}")]
+    ColumnError {
+        err: ColumnParsingError,
+        command: String,
+    },
+
+    // TIMING error.
+    #[error("Failed to parse TIMING:\n{command}. \n{err}")]
+    TimingError {
+        err: TimingParsingError,
+        command: String,
+    },
+
+    // SESSION error.
+    #[error("Failed to parse SESSION:\n{command}. \n{err}")]
+    SessionError {
+        err: SessionParsingError,
+        command: String,
+    },
 }
 
+/// Strips leading keyword (case insensitive) which should be followed by whitespace or nothing,
+/// and the trailing `;`.
+fn strip_keyword<'a>(command: &'a str, keywords: &[&str]) -> Option<&'a str> {
+    strip_keyword_only(command, keywords).map(|x| x.trim_end_matches(';').trim_end())
+}
+
+/// Like [`strip_keyword`], but the trailing `;` is kept.
+fn strip_keyword_only<'a>(command: &'a str, keywords: &[&str]) -> Option<&'a str> {
+    keywords.iter().find_map(|keyword| {
+        let head = command.get(..keyword.len())?;
+        let rest = &command[keyword.len()..];
+        (head.eq_ignore_ascii_case(keyword)
+            && (rest.is_empty() || rest.starts_with(char::is_whitespace)))
+        .then(|| rest.trim())
+    })
+}
+
+/// Strips the trailing `;` of `SET`, unless it's the value of `DELIMITER ;`.
+fn strip_set_terminator(set: &str) -> &str {
+    let Some(rest) = set.strip_suffix(';') else {
+        return set;
+    };
+    match rest.split_whitespace().last() {
+        Some(option) if option.eq_ignore_ascii_case("delimiter") => set,
+        _ => rest.trim_end(),
+    }
+}
+
+fn parse_spool(spool: &str) -> SpoolCommand {
+    if spool.is_empty() {
+        return SpoolCommand::Status;
+    }
+    if spool.eq_ignore_ascii_case("off") {
+        return SpoolCommand::Off;
+    }
+    let (path, mode) = match spool.rsplit_once(char::is_whitespace) {
+        Some((path, mode)) if mode.eq_ignore_ascii_case("create") => (path, SpoolMode::Create),
+        Some((path, mode)) if mode.eq_ignore_ascii_case("replace") => (path, SpoolMode::Replace),
+        Some((path, mode)) if mode.eq_ignore_ascii_case("append") => (path, SpoolMode::Append),
+        _ => (spool, SpoolMode::Replace),
+    };
+    SpoolCommand::To(path.trim().trim_matches('"').to_string(), mode)
+}
+
+fn parse_variable_command(command: &str) -> Option<Result<Command, ParseError>> {
+    if let Some(def) = strip_keyword(command, &["variable", "var"]) {
+        Some(if def.is_empty() {
+            Ok(Command::Internal(InternalCommand::Variable(None)))
+        } else if !def.contains(char::is_whitespace) {
+            Ok(Command::Internal(InternalCommand::Variable(Some(
+                def.to_uppercase(),
+            ))))
+        } else {
+            parse_variable_definition(def)
+                .map(|x| Command::Internal(InternalCommand::DefineVariable(x)))
+                .map_err(|err| ParseError::VariableError {
+                    err,
+                    command: command.to_string(),
+                })
+        })
+    } else if let Some(name) = strip_keyword(command, &["print"]) {
+        Some(Ok(Command::Internal(InternalCommand::Print(
+            (!name.is_empty()).then(|| name.to_uppercase()),
+        ))))
+    } else {
+        strip_keyword(command, &["execute", "exec"]).map(|statement| {
+            Ok(Command::Internal(InternalCommand::Execute(
+                statement.to_string(),
+            )))
+        })
+    }
+}
+
+/// Names of internal commands, for completion.
+pub const COMMANDS: &[&str] = &[
+    "conn",
+    "desc",
+    "disconnect",
+    "reconnect",
+    "refresh",
+    "exit",
+    "variable",
+    "print",
+    "execute",
+    "spool",
+    "set",
+    "column",
+    "timing",
+    "session",
+    "whenever",
+    "edit",
+];
+
 pub fn parse_internal_command(command: &str) -> Option<Result<Command, ParseError>> {
     if let Some(table_or_view) = command.strip_prefix("desc ") {
         Some(if table_or_view.ends_with(';') {
@@ -31,18 +173,111 @@ pub fn parse_internal_command(command: &str) -> Option<Result<Command, ParseErro
         })
     } else if command == "exit" {
         Some(Ok(Command::Internal(InternalCommand::Exit)))
+    } else if let Some(internal) = match command
+        .trim_end_matches(';')
+        .trim_end()
+        .to_lowercase()
+        .as_str()
+    {
+        "disconnect" | "disc" => Some(InternalCommand::Disconnect),
+        "reconnect" => Some(InternalCommand::Reconnect),
+        "refresh" => Some(InternalCommand::Refresh),
+        _ => None,
+    } {
+        Some(Ok(Command::Internal(internal)))
+    } else if let Some(file) = strip_keyword(command, &["edit", "ed"]) {
+        Some(Ok(Command::Internal(InternalCommand::Edit(
+            (!file.is_empty()).then(|| file.to_string()),
+        ))))
+    } else if let Some(command) = parse_variable_command(command) {
+        Some(command)
+    } else if let Some(spool) = strip_keyword(command, &["spool", "spo"]) {
+        Some(Ok(Command::Internal(InternalCommand::Spool(parse_spool(
+            spool,
+        )))))
+    } else if let Some(set) = strip_keyword_only(command, &["set"])
+        .map(strip_set_terminator)
+        .filter(|x| {
+            // SQL statements
+            strip_keyword(x, &["transaction", "role", "constraint", "constraints"]).is_none()
+        })
+    {
+        Some(
+            parse_set(set)
+                .map(|x| Command::Internal(InternalCommand::Set(x)))
+                .map_err(|err| ParseError::SetError {
+                    err,
+                    command: command.to_string(),
+                }),
+        )
+    } else if let Some(column) = strip_keyword(command, &["column", "col"]) {
+        Some(
+            parse_column(column)
+                .map(|x| Command::Internal(InternalCommand::Column(x)))
+                .map_err(|err| ParseError::ColumnError {
+                    err,
+                    command: command.to_string(),
+                }),
+        )
+    } else if let Some(timing) = strip_keyword(command, &["timing", "timi"]) {
+        Some(
+            parse_timing(timing)
+                .map(|x| Command::Internal(InternalCommand::Timing(x)))
+                .map_err(|err| ParseError::TimingError {
+                    err,
+                    command: command.to_string(),
+                }),
+        )
+    } else if let Some(session) = strip_keyword(command, &["session"]) {
+        Some(
+            parse_session(session)
+                .map(|x| Command::Internal(InternalCommand::Session(x)))
+                .map_err(|err| ParseError::SessionError {
+                    err,
+                    command: command.to_string(),
+                }),
+        )
+    } else if let Some(whenever) = strip_keyword(command, &["whenever"]) {
+        Some(
+            parse_whenever(whenever)
+                .map(|(condition, action)| {
+                    Command::Internal(InternalCommand::Whenever(condition, action))
+                })
+                .map_err(|err| ParseError::WheneverError {
+                    err,
+                    command: command.to_string(),
+                }),
+        )
     } else {
         command.strip_prefix("conn ").map(|conn_str| {
-            parse_connection_string(conn_str)
-                .map(|x| Command::Internal(InternalCommand::Connect(x)))
+            parse_conn(conn_str)
+                .map(|(name, x)| Command::Internal(InternalCommand::Connect(name, x)))
                 .map_err(|err| ParseError::ParsingError {
                     err,
-                    command: command.to_string(),
+                    command: redact_command(command).to_string(),
                 })
         })
     }
 }
 
+/// Removes password from `conn` command, e.g. `conn sys/pwd@host` to `conn sys@host`, even if
+/// the command is invalid.
+pub fn redact_command(command: &str) -> Cow<'_, str> {
+    if strip_keyword_only(command.trim_start(), &["conn", "connect"]).is_none() {
+        return Cow::Borrowed(command);
+    }
+    // the password is between `/` and the last `@`, or till the end without host
+    match command.find('/') {
+        Some(slash) => {
+            let end = command[slash..]
+                .rfind('@')
+                .map_or(command.len(), |x| slash + x);
+            Cow::Owned(format!("{}{}", &command[..slash], &command[end..]))
+        }
+        None => Cow::Borrowed(command),
+    }
+}
+
 pub fn parse_command(command: &str) -> Result<Command, ParseError> {
     if command.is_empty() {
         Err(ParseError::Empty) // TODO should be an error? or a empty command
@@ -61,6 +296,7 @@ pub fn parse_command(command: &str) -> Result<Command, ParseError> {
             });
         if (single_line && (last_line_is_comment || command.ends_with(';')))
             || (!single_line && last_line == "/")
+            || command.trim_end().ends_with("\\G")
         {
             Ok(Command::SQL(command.to_string()))
         } else {
@@ -71,9 +307,12 @@ pub fn parse_command(command: &str) -> Result<Command, ParseError> {
 
 #[cfg(test)]
 mod test {
-    use crate::command::{parser::ParseError, Command, InternalCommand};
+    use crate::command::{
+        parser::ParseError, Command, InternalCommand, ResultFormat, SessionCommand, Setting,
+        SpoolCommand, SpoolMode, TimingCommand,
+    };
 
-    use super::parse_command;
+    use super::{parse_command, redact_command};
 
     #[test]
     fn parse() {
@@ -116,11 +355,213 @@ mod test {
         // conn
         assert!(matches!(
             parse_command("conn sys/pwd@host:9999"),
-            Ok(Command::Internal(InternalCommand::Connect(_)))
+            Ok(Command::Internal(InternalCommand::Connect(None, _)))
+        ));
+        assert!(matches!(
+            parse_command("conn -n dev sys/pwd@host:9999"),
+            Ok(Command::Internal(InternalCommand::Connect(Some(name), _))) if name == "dev"
+        ));
+        assert!(matches!(
+            parse_command("conn -n"),
+            Err(ParseError::ParsingError { .. })
         ));
         assert!(matches!(
             parse_command("conn sys/pwd@host:9999;"),
             Err(ParseError::ParsingError { .. })
         ));
+
+        // disconnect
+        assert!(matches!(
+            parse_command("disc;"),
+            Ok(Command::Internal(InternalCommand::Disconnect))
+        ));
+        assert!(matches!(
+            parse_command("RECONNECT"),
+            Ok(Command::Internal(InternalCommand::Reconnect))
+        ));
+        assert!(matches!(
+            parse_command("refresh;"),
+            Ok(Command::Internal(InternalCommand::Refresh))
+        ));
+
+        // edit
+        assert!(matches!(
+            parse_command("ed"),
+            Ok(Command::Internal(InternalCommand::Edit(None)))
+        ));
+        assert!(matches!(
+            parse_command("EDIT report.sql;"),
+            Ok(Command::Internal(InternalCommand::Edit(Some(file)))) if file == "report.sql"
+        ));
+        assert!(matches!(parse_command("editions;"), Ok(Command::SQL(_))));
+
+        // variable
+        assert!(matches!(
+            parse_command("var"),
+            Ok(Command::Internal(InternalCommand::Variable(None)))
+        ));
+        assert!(matches!(
+            parse_command("VARIABLE x;"),
+            Ok(Command::Internal(InternalCommand::Variable(Some(x)))) if x == "X"
+        ));
+        assert!(matches!(
+            parse_command("variable x varchar(20)"),
+            Ok(Command::Internal(InternalCommand::DefineVariable(_)))
+        ));
+        assert!(matches!(
+            parse_command("variable x blob"),
+            Err(ParseError::VariableError { .. })
+        ));
+        assert!(matches!(parse_command("variance;"), Ok(Command::SQL(_))));
+
+        // expanded
+        assert!(matches!(
+            parse_command("select * from dual\\G"),
+            Ok(Command::SQL(sql)) if sql.ends_with("\\G")
+        ));
+        assert!(matches!(
+            parse_command("select *\nfrom dual\\G"),
+            Ok(Command::SQL(_))
+        ));
+
+        // print
+        assert!(matches!(
+            parse_command("print x"),
+            Ok(Command::Internal(InternalCommand::Print(Some(x)))) if x == "X"
+        ));
+
+        // exec
+        assert!(matches!(
+            parse_command("exec proc(:x);"),
+            Ok(Command::Internal(InternalCommand::Execute(x))) if x == "proc(:x)"
+        ));
+
+        // spool
+        assert!(matches!(
+            parse_command("spool"),
+            Ok(Command::Internal(InternalCommand::Spool(
+                SpoolCommand::Status
+            )))
+        ));
+        assert!(matches!(
+            parse_command("SPOOL OFF"),
+            Ok(Command::Internal(InternalCommand::Spool(SpoolCommand::Off)))
+        ));
+        assert!(matches!(
+            parse_command("spool \"my result.txt\" append;"),
+            Ok(Command::Internal(InternalCommand::Spool(SpoolCommand::To(file, SpoolMode::Append))))
+                if file == "my result.txt"
+        ));
+        assert!(matches!(
+            parse_command("spool result.txt"),
+            Ok(Command::Internal(InternalCommand::Spool(SpoolCommand::To(file, SpoolMode::Replace))))
+                if file == "result.txt"
+        ));
+
+        // set
+        assert!(matches!(
+            parse_command("set markup json;"),
+            Ok(Command::Internal(InternalCommand::Set(_)))
+        ));
+        assert!(matches!(
+            parse_command("set markup csv on delimiter ;"),
+            Ok(Command::Internal(InternalCommand::Set(Setting::Markup(
+                ResultFormat::Csv {
+                    delimiter: ';',
+                    quote: true
+                }
+            ))))
+        ));
+        assert!(matches!(
+            parse_command("set markup csv on delimiter ; quote off;"),
+            Ok(Command::Internal(InternalCommand::Set(Setting::Markup(
+                ResultFormat::Csv {
+                    delimiter: ';',
+                    quote: false
+                }
+            ))))
+        ));
+        assert!(matches!(
+            parse_command("set markup csv on delimiter |;"),
+            Ok(Command::Internal(InternalCommand::Set(Setting::Markup(
+                ResultFormat::Csv {
+                    delimiter: '|',
+                    quote: true
+                }
+            ))))
+        ));
+        assert!(matches!(
+            parse_command("set unknown on"),
+            Err(ParseError::SetError { .. })
+        ));
+        assert!(matches!(
+            parse_command("set transaction read only"),
+            Err(ParseError::Incomplete(_))
+        ));
+
+        // column
+        assert!(matches!(
+            parse_command("col sal format 999,999.99;"),
+            Ok(Command::Internal(InternalCommand::Column(_)))
+        ));
+        assert!(matches!(
+            parse_command("column sal justify left"),
+            Err(ParseError::ColumnError { .. })
+        ));
+
+        // timing
+        assert!(matches!(
+            parse_command("timing start;"),
+            Ok(Command::Internal(InternalCommand::Timing(
+                TimingCommand::Start(None)
+            )))
+        ));
+        assert!(matches!(
+            parse_command("timing pause"),
+            Err(ParseError::TimingError { .. })
+        ));
+
+        // redact
+        assert_eq!(
+            redact_command("conn sys/pwd@host:1601"),
+            "conn sys@host:1601"
+        );
+        assert_eq!(redact_command("conn /pwd"), "conn ");
+        assert_eq!(redact_command("conn sys@prod"), "conn sys@prod");
+        assert_eq!(
+            redact_command("conn -n dev sys/pwd@host"),
+            "conn -n dev sys@host"
+        );
+        assert_eq!(redact_command("select 1 from dual;"), "select 1 from dual;");
+        // invalid ones
+        assert_eq!(
+            redact_command("conn sys/p@ss@host:port"),
+            "conn sys@host:port"
+        );
+        assert_eq!(redact_command("CONNECT sys/pwd"), "CONNECT sys");
+        assert!(matches!(
+            parse_command("conn sys/pwd@host:port"),
+            Err(ParseError::ParsingError { command, .. }) if command == "conn sys@host:port"
+        ));
+
+        // session
+        assert!(matches!(
+            parse_command("session use dev;"),
+            Ok(Command::Internal(InternalCommand::Session(SessionCommand::Use(name)))) if name == "dev"
+        ));
+        assert!(matches!(
+            parse_command("session open dev"),
+            Err(ParseError::SessionError { .. })
+        ));
+
+        // whenever
+        assert!(matches!(
+            parse_command("WHENEVER SQLERROR EXIT SQL.SQLCODE;"),
+            Ok(Command::Internal(InternalCommand::Whenever(..)))
+        ));
+        assert!(matches!(
+            parse_command("whenever sqlerror stop"),
+            Err(ParseError::WheneverError { .. })
+        ));
     }
 }
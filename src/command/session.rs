@@ -0,0 +1,65 @@
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionCommand {
+    /// Lists open sessions.
+    List,
+    /// Switches to the session.
+    Use(String),
+    /// Disconnects the session.
+    Close(String),
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SessionParsingError {
+    #[error("Expected LIST, USE or CLOSE")]
+    Action,
+
+    #[error("Expected session name")]
+    Name,
+}
+
+/// Parses `SESSION [LIST|USE name|CLOSE name]`.
+pub fn parse_session(session: &str) -> Result<SessionCommand, SessionParsingError> {
+    let (action, name) = session
+        .split_once(char::is_whitespace)
+        .map(|(action, name)| (action, name.trim()))
+        .unwrap_or((session, ""));
+    let name = || {
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            Err(SessionParsingError::Name)
+        } else {
+            Ok(name.to_string())
+        }
+    };
+    match action.to_uppercase().as_str() {
+        "" | "LIST" => Ok(SessionCommand::List),
+        "USE" => Ok(SessionCommand::Use(name()?)),
+        "CLOSE" => Ok(SessionCommand::Close(name()?)),
+        _ => Err(SessionParsingError::Action),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_session, SessionCommand, SessionParsingError};
+
+    #[test]
+    fn parse() {
+        assert_eq!(parse_session(""), Ok(SessionCommand::List));
+        assert_eq!(parse_session("list"), Ok(SessionCommand::List));
+        assert_eq!(
+            parse_session("USE dev"),
+            Ok(SessionCommand::Use("dev".to_string()))
+        );
+        assert_eq!(
+            parse_session("close prod"),
+            Ok(SessionCommand::Close("prod".to_string()))
+        );
+        assert_eq!(parse_session("use"), Err(SessionParsingError::Name));
+        assert_eq!(
+            parse_session("switch dev"),
+            Err(SessionParsingError::Action)
+        );
+    }
+}
@@ -0,0 +1,435 @@
+use std::str::FromStr;
+
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResultFormat {
+    #[default]
+    Table,
+    Csv {
+        delimiter: char,
+        quote: bool,
+    },
+    Tsv,
+    /// JSON array of objects.
+    Json,
+    /// Newline delimited JSON, one object per line.
+    NdJson,
+    Markdown,
+    Html,
+    /// Plain aligned text.
+    Text,
+}
+
+impl FromStr for ResultFormat {
+    type Err = SetParsingError;
+
+    /// Parses like `CSV [ON|OFF] [DELIMITER c] [QUOTE {ON|OFF}]`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |value: &str| SetParsingError::Value {
+            option: "MARKUP".to_string(),
+            value: value.to_string(),
+        };
+        let tokens = s.split_whitespace().collect::<Vec<_>>();
+        let (name, mut options) = tokens.split_first().ok_or_else(|| invalid(s))?;
+        let mut format = match name.to_uppercase().as_str() {
+            "TABLE" => ResultFormat::Table,
+            "CSV" => ResultFormat::Csv {
+                delimiter: ',',
+                quote: true,
+            },
+            "TSV" => ResultFormat::Tsv,
+            "JSON" => ResultFormat::Json,
+            "NDJSON" => ResultFormat::NdJson,
+            "MARKDOWN" | "MD" => ResultFormat::Markdown,
+            "HTML" => ResultFormat::Html,
+            "TEXT" => ResultFormat::Text,
+            _ => return Err(invalid(name)),
+        };
+
+        // `SET MARKUP CSV ON` as in sqlplus
+        match options.first().map(|x| x.to_uppercase()).as_deref() {
+            Some("ON") => options = &options[1..],
+            Some("OFF") => {
+                options = &options[1..];
+                format = ResultFormat::Table;
+            }
+            _ => {}
+        }
+
+        while let [option, value, rest @ ..] = options {
+            match (option.to_uppercase().as_str(), &mut format) {
+                ("DELIMITER", ResultFormat::Csv { delimiter, .. }) => {
+                    let value = value.trim_matches(['\'', '"']);
+                    *delimiter = if value.eq_ignore_ascii_case("tab") {
+                        '\t'
+                    } else {
+                        let mut chars = value.chars();
+                        match (chars.next(), chars.next()) {
+                            (Some(ch), None) => ch,
+                            _ => return Err(invalid(value)),
+                        }
+                    };
+                }
+                ("QUOTE", ResultFormat::Csv { quote, .. }) => {
+                    *quote = parse_switch(value).ok_or_else(|| invalid(value))?;
+                }
+                _ => return Err(invalid(option)),
+            }
+            options = rest;
+        }
+        match options {
+            [] => Ok(format),
+            [option, ..] => Err(invalid(option)),
+        }
+    }
+}
+
+/// Whether to display each row as `COLUMN : value` lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Expanded {
+    #[default]
+    Off,
+    On,
+    /// Only when rows are wider than the terminal.
+    Auto,
+}
+
+/// When to send output to the pager.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PagerMode {
+    Always,
+    /// Only when output is wider or higher than the terminal.
+    #[default]
+    Auto,
+    Never,
+}
+
+/// Key bindings of the line editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EditMode {
+    Emacs,
+    #[default]
+    Vi,
+}
+
+/// How candidates of completion are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompletionMode {
+    /// Cycles through candidates in the line.
+    #[default]
+    Circular,
+    /// Completes the common prefix and lists the rest, like bash.
+    List,
+}
+
+/// Options changed by `SET`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Setting {
+    Markup(ResultFormat),
+    /// Rows between repeated headings, `0` for never.
+    PageSize(usize),
+    Expanded(Expanded),
+    /// Wraps values wider than their columns, otherwise truncates them.
+    Wrap(bool),
+    /// Max width of text columns, `0` for unlimited.
+    MaxColumnWidth(usize),
+    Pager(PagerMode),
+    /// Command to run as the pager, e.g. `less -RS`.
+    PagerCommand(String),
+    /// Shows elapsed time of each statement.
+    Timing(bool),
+    /// Connects again when the connection is lost.
+    Reconnect(bool),
+    /// Name of a bundled highlighting theme, or path of a `.tmTheme` file.
+    Theme(String),
+    EditMode(EditMode),
+    Completion(CompletionMode),
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SetParsingError {
+    #[error("Unknown option: {0}")]
+    Unknown(String),
+
+    #[error("Invalid value for {option}: {value}")]
+    Value { option: String, value: String },
+}
+
+/// Options of `SET` and their values, for completion.
+pub const SETTINGS: &[(&str, &[&str])] = &[
+    (
+        "MARKUP",
+        &[
+            "TABLE", "CSV", "TSV", "JSON", "NDJSON", "MARKDOWN", "HTML", "TEXT",
+        ],
+    ),
+    ("PAGESIZE", &[]),
+    ("EXPANDED", &["ON", "OFF", "AUTO"]),
+    ("WRAP", &["ON", "OFF"]),
+    ("MAXCOLWIDTH", &[]),
+    ("PAGER", &["ALWAYS", "AUTO", "NEVER"]),
+    ("TIMING", &["ON", "OFF"]),
+    ("RECONNECT", &["ON", "OFF"]),
+    (
+        "THEME",
+        &[
+            "base16-ocean.dark",
+            "base16-eighties.dark",
+            "base16-mocha.dark",
+            "base16-ocean.light",
+            "InspiredGitHub",
+            "Solarized (dark)",
+            "Solarized (light)",
+        ],
+    ),
+    ("EDITMODE", &["EMACS", "VI"]),
+    ("COMPLETION", &["CIRCULAR", "LIST"]),
+];
+
+/// Parses `SET` command like `MARKUP CSV`.
+pub fn parse_set(set: &str) -> Result<Setting, SetParsingError> {
+    let (option, value) = set
+        .split_once(char::is_whitespace)
+        .map(|(option, value)| (option, value.trim()))
+        .unwrap_or((set, ""));
+    match option.to_uppercase().as_str() {
+        "MARKUP" | "MARK" => Ok(Setting::Markup(value.parse()?)),
+        "PAGESIZE" | "PAGES" => {
+            value
+                .parse()
+                .map(Setting::PageSize)
+                .map_err(|_| SetParsingError::Value {
+                    option: "PAGESIZE".to_string(),
+                    value: value.to_string(),
+                })
+        }
+        "EXPANDED" | "EXPAND" => match value.to_uppercase().as_str() {
+            "AUTO" => Ok(Setting::Expanded(Expanded::Auto)),
+            _ => parse_switch(value)
+                .map(|on| Setting::Expanded(if on { Expanded::On } else { Expanded::Off }))
+                .ok_or_else(|| SetParsingError::Value {
+                    option: "EXPANDED".to_string(),
+                    value: value.to_string(),
+                }),
+        },
+        "WRAP" | "WRA" => {
+            parse_switch(value)
+                .map(Setting::Wrap)
+                .ok_or_else(|| SetParsingError::Value {
+                    option: "WRAP".to_string(),
+                    value: value.to_string(),
+                })
+        }
+        "MAXCOLWIDTH" => {
+            value
+                .parse()
+                .map(Setting::MaxColumnWidth)
+                .map_err(|_| SetParsingError::Value {
+                    option: "MAXCOLWIDTH".to_string(),
+                    value: value.to_string(),
+                })
+        }
+        "TIMING" | "TIMI" => {
+            parse_switch(value)
+                .map(Setting::Timing)
+                .ok_or_else(|| SetParsingError::Value {
+                    option: "TIMING".to_string(),
+                    value: value.to_string(),
+                })
+        }
+        "RECONNECT" => {
+            parse_switch(value)
+                .map(Setting::Reconnect)
+                .ok_or_else(|| SetParsingError::Value {
+                    option: "RECONNECT".to_string(),
+                    value: value.to_string(),
+                })
+        }
+        "PAGER" => match value.to_uppercase().as_str() {
+            "ALWAYS" => Ok(Setting::Pager(PagerMode::Always)),
+            "AUTO" | "ON" => Ok(Setting::Pager(PagerMode::Auto)),
+            "NEVER" | "OFF" => Ok(Setting::Pager(PagerMode::Never)),
+            "" => Err(SetParsingError::Value {
+                option: "PAGER".to_string(),
+                value: value.to_string(),
+            }),
+            _ => Ok(Setting::PagerCommand(
+                value.trim_matches(['\'', '"']).to_string(),
+            )),
+        },
+        "THEME" => match value.trim_matches(['\'', '"']) {
+            "" => Err(SetParsingError::Value {
+                option: "THEME".to_string(),
+                value: value.to_string(),
+            }),
+            theme => Ok(Setting::Theme(theme.to_string())),
+        },
+        "EDITMODE" => match value.to_uppercase().as_str() {
+            "EMACS" => Ok(Setting::EditMode(EditMode::Emacs)),
+            "VI" => Ok(Setting::EditMode(EditMode::Vi)),
+            _ => Err(SetParsingError::Value {
+                option: "EDITMODE".to_string(),
+                value: value.to_string(),
+            }),
+        },
+        "COMPLETION" => match value.to_uppercase().as_str() {
+            "CIRCULAR" => Ok(Setting::Completion(CompletionMode::Circular)),
+            "LIST" => Ok(Setting::Completion(CompletionMode::List)),
+            _ => Err(SetParsingError::Value {
+                option: "COMPLETION".to_string(),
+                value: value.to_string(),
+            }),
+        },
+        _ => Err(SetParsingError::Unknown(option.to_string())),
+    }
+}
+
+fn parse_switch(value: &str) -> Option<bool> {
+    match value.to_uppercase().as_str() {
+        "ON" => Some(true),
+        "OFF" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        parse_set, CompletionMode, EditMode, Expanded, PagerMode, ResultFormat, SetParsingError,
+        Setting,
+    };
+
+    #[test]
+    fn markup() {
+        assert_eq!(
+            parse_set("markup csv"),
+            Ok(Setting::Markup(ResultFormat::Csv {
+                delimiter: ',',
+                quote: true
+            }))
+        );
+        assert_eq!(
+            parse_set("MARKUP CSV ON DELIMITER '|' QUOTE OFF"),
+            Ok(Setting::Markup(ResultFormat::Csv {
+                delimiter: '|',
+                quote: false
+            }))
+        );
+        assert_eq!(
+            parse_set("markup html off"),
+            Ok(Setting::Markup(ResultFormat::Table))
+        );
+        assert_eq!("ndjson".parse::<ResultFormat>(), Ok(ResultFormat::NdJson));
+
+        assert!(matches!(
+            parse_set("markup json quote on"),
+            Err(SetParsingError::Value { .. })
+        ));
+        assert!(matches!(
+            parse_set("markup csv delimiter ab"),
+            Err(SetParsingError::Value { .. })
+        ));
+        assert!(matches!(
+            parse_set("markup"),
+            Err(SetParsingError::Value { .. })
+        ));
+    }
+
+    #[test]
+    fn pagesize() {
+        assert_eq!(parse_set("pagesize 20"), Ok(Setting::PageSize(20)));
+        assert!(matches!(
+            parse_set("pages -1"),
+            Err(SetParsingError::Value { .. })
+        ));
+    }
+
+    #[test]
+    fn expanded() {
+        assert_eq!(
+            parse_set("expanded auto"),
+            Ok(Setting::Expanded(Expanded::Auto))
+        );
+        assert_eq!(parse_set("EXPAND ON"), Ok(Setting::Expanded(Expanded::On)));
+        assert!(matches!(
+            parse_set("expanded yes"),
+            Err(SetParsingError::Value { .. })
+        ));
+    }
+
+    #[test]
+    fn wrap() {
+        assert_eq!(parse_set("wrap off"), Ok(Setting::Wrap(false)));
+    }
+
+    #[test]
+    fn timing() {
+        assert_eq!(parse_set("timing on"), Ok(Setting::Timing(true)));
+    }
+
+    #[test]
+    fn reconnect() {
+        assert_eq!(parse_set("reconnect on"), Ok(Setting::Reconnect(true)));
+    }
+
+    #[test]
+    fn theme() {
+        assert_eq!(
+            parse_set("theme \"Solarized (dark)\""),
+            Ok(Setting::Theme("Solarized (dark)".to_string()))
+        );
+    }
+
+    #[test]
+    fn editmode() {
+        assert_eq!(
+            parse_set("editmode emacs"),
+            Ok(Setting::EditMode(EditMode::Emacs))
+        );
+        assert!(matches!(
+            parse_set("editmode nano"),
+            Err(SetParsingError::Value { .. })
+        ));
+    }
+
+    #[test]
+    fn completion() {
+        assert_eq!(
+            parse_set("COMPLETION LIST"),
+            Ok(Setting::Completion(CompletionMode::List))
+        );
+    }
+
+    #[test]
+    fn maxcolwidth() {
+        assert_eq!(parse_set("maxcolwidth 40"), Ok(Setting::MaxColumnWidth(40)));
+    }
+
+    #[test]
+    fn pager() {
+        assert_eq!(parse_set("pager off"), Ok(Setting::Pager(PagerMode::Never)));
+        assert_eq!(parse_set("pager on"), Ok(Setting::Pager(PagerMode::Auto)));
+        assert_eq!(parse_set("PAGER AUTO"), Ok(Setting::Pager(PagerMode::Auto)));
+        assert_eq!(
+            parse_set("pager always"),
+            Ok(Setting::Pager(PagerMode::Always))
+        );
+        assert_eq!(
+            parse_set("pager 'less -RS'"),
+            Ok(Setting::PagerCommand("less -RS".to_string()))
+        );
+        assert!(matches!(
+            parse_set("pager"),
+            Err(SetParsingError::Value { .. })
+        ));
+    }
+
+    #[test]
+    fn unknown() {
+        assert_eq!(
+            parse_set("unknown on"),
+            Err(SetParsingError::Unknown("unknown".to_string()))
+        );
+    }
+}
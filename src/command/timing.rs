@@ -0,0 +1,55 @@
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimingCommand {
+    /// Shows how many timers are running.
+    Status,
+    Start(Option<String>),
+    /// Shows elapsed time of the timer, the latest one if not named.
+    Show(Option<String>),
+    /// Shows elapsed time of the timer and removes it, the latest one if not named.
+    Stop(Option<String>),
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TimingParsingError {
+    #[error("Expected START, SHOW or STOP")]
+    Action,
+}
+
+/// Parses `TIMING [START|SHOW|STOP [name]]`.
+pub fn parse_timing(timing: &str) -> Result<TimingCommand, TimingParsingError> {
+    if timing.is_empty() {
+        return Ok(TimingCommand::Status);
+    }
+    let (action, name) = timing
+        .split_once(char::is_whitespace)
+        .map(|(action, name)| (action, Some(name.trim().to_string())))
+        .unwrap_or((timing, None));
+    match action.to_uppercase().as_str() {
+        "START" => Ok(TimingCommand::Start(name)),
+        "SHOW" => Ok(TimingCommand::Show(name)),
+        "STOP" => Ok(TimingCommand::Stop(name)),
+        _ => Err(TimingParsingError::Action),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_timing, TimingCommand, TimingParsingError};
+
+    #[test]
+    fn parse() {
+        assert_eq!(parse_timing(""), Ok(TimingCommand::Status));
+        assert_eq!(
+            parse_timing("start load data"),
+            Ok(TimingCommand::Start(Some("load data".to_string())))
+        );
+        assert_eq!(parse_timing("SHOW"), Ok(TimingCommand::Show(None)));
+        assert_eq!(
+            parse_timing("stop report"),
+            Ok(TimingCommand::Stop(Some("report".to_string())))
+        );
+        assert_eq!(parse_timing("reset"), Err(TimingParsingError::Action));
+    }
+}
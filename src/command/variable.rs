@@ -0,0 +1,271 @@
+use std::fmt::Display;
+
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VariableType {
+    Number,
+    Char(usize),
+    NChar(usize),
+    VarChar(usize),
+    NVarChar(usize),
+}
+
+impl VariableType {
+    /// Max length in bytes of a value of this type.
+    pub fn size(&self) -> usize {
+        match self {
+            VariableType::Number => 64,
+            VariableType::Char(size)
+            | VariableType::NChar(size)
+            | VariableType::VarChar(size)
+            | VariableType::NVarChar(size) => size * 4, // utf-8
+        }
+    }
+}
+
+impl Display for VariableType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VariableType::Number => write!(f, "NUMBER"),
+            VariableType::Char(size) => write!(f, "CHAR({size})"),
+            VariableType::NChar(size) => write!(f, "NCHAR({size})"),
+            VariableType::VarChar(size) => write!(f, "VARCHAR({size})"),
+            VariableType::NVarChar(size) => write!(f, "NVARCHAR({size})"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variable {
+    pub name: String,
+    pub type_: VariableType,
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum VariableParsingError {
+    #[error("Invalid variable name: {0}")]
+    Name(String),
+
+    #[error("Unsupported variable type: {0}")]
+    Type(String),
+
+    #[error("Invalid value: {0}")]
+    Value(String),
+}
+
+/// Parses variable definition like `name type [= value]`.
+pub fn parse_variable_definition(def: &str) -> Result<Variable, VariableParsingError> {
+    let (def, value) = match def.split_once('=') {
+        Some((def, value)) => (def, Some(value.trim())),
+        None => (def, None),
+    };
+    let (name, type_) = def
+        .trim()
+        .split_once(char::is_whitespace)
+        .ok_or_else(|| VariableParsingError::Type(String::new()))?;
+    if !is_identifier(name) {
+        return Err(VariableParsingError::Name(name.to_string()));
+    }
+
+    let type_str = type_
+        .chars()
+        .filter(|x| !x.is_whitespace())
+        .collect::<String>()
+        .to_uppercase();
+    let (base, size) = match type_str.split_once('(') {
+        Some((base, size)) => {
+            let size = size
+                .strip_suffix(')')
+                .map(|x| x.trim_end_matches("CHAR").trim_end_matches("BYTE"))
+                .and_then(|x| x.parse::<usize>().ok())
+                .filter(|x| *x > 0)
+                .ok_or_else(|| VariableParsingError::Type(type_.trim().to_string()))?;
+            (base, Some(size))
+        }
+        None => (type_str.as_str(), None),
+    };
+    let size = size.unwrap_or(1);
+    let type_ = match base {
+        "NUMBER" | "INTEGER" | "INT" | "BIGINT" | "FLOAT" | "DOUBLE" | "BINARY_INTEGER"
+        | "BINARY_FLOAT" | "BINARY_DOUBLE" => VariableType::Number,
+        "CHAR" => VariableType::Char(size),
+        "NCHAR" => VariableType::NChar(size),
+        "VARCHAR" | "VARCHAR2" => VariableType::VarChar(size),
+        "NVARCHAR" | "NVARCHAR2" => VariableType::NVarChar(size),
+        _ => return Err(VariableParsingError::Type(type_.trim().to_string())),
+    };
+
+    let value = match value {
+        Some(value) => Some(parse_literal(value, &type_)?),
+        None => None,
+    };
+
+    Ok(Variable {
+        name: name.to_uppercase(),
+        type_,
+        value,
+    })
+}
+
+fn parse_literal(value: &str, type_: &VariableType) -> Result<String, VariableParsingError> {
+    let invalid = || VariableParsingError::Value(value.to_string());
+    match type_ {
+        VariableType::Number if is_number(value) => Ok(value.to_string()),
+        VariableType::Number => Err(invalid()),
+        VariableType::Char(size)
+        | VariableType::NChar(size)
+        | VariableType::VarChar(size)
+        | VariableType::NVarChar(size) => value
+            .strip_prefix('\'')
+            .and_then(|x| x.strip_suffix('\''))
+            .map(|x| x.replace("''", "'"))
+            .filter(|x| x.chars().count() <= *size)
+            .ok_or_else(invalid),
+    }
+}
+
+/// A numeric literal, e.g. `-1`, `.5`, `1.` or `6.02E23`, but not `inf` or `NaN`.
+fn is_number(value: &str) -> bool {
+    let digits = |x: &str| x.len() - x.trim_start_matches(|x: char| x.is_ascii_digit()).len();
+    let value = value.strip_prefix(['+', '-']).unwrap_or(value);
+    let integer = digits(value);
+    let mut rest = &value[integer..];
+    let mut fraction = 0;
+    if let Some(x) = rest.strip_prefix('.') {
+        fraction = digits(x);
+        rest = &x[fraction..];
+    }
+    if integer + fraction == 0 {
+        return false;
+    }
+    match rest.strip_prefix(['e', 'E']) {
+        Some(exponent) => {
+            let exponent = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+            !exponent.is_empty() && digits(exponent) == exponent.len()
+        }
+        None => rest.is_empty(),
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    name.starts_with(|x: char| x.is_alphabetic())
+        && name
+            .chars()
+            .all(|x| x.is_alphanumeric() || "_$#".contains(x))
+}
+
+/// Finds names of bind variables(e.g. `:name`) in SQL in order of appearance.
+///
+/// Placeholders in string literals, quoted identifiers and comments are ignored, so are
+/// records like `:new.id` in triggers.
+pub fn find_bind_variables(sql: &str) -> Vec<String> {
+    let mut variables = vec![];
+    let mut chars = sql.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\'' | '"' => {
+                for x in chars.by_ref() {
+                    if x == ch {
+                        break;
+                    }
+                }
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                for x in chars.by_ref() {
+                    if x == '\n' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut last = ' ';
+                for x in chars.by_ref() {
+                    if last == '*' && x == '/' {
+                        break;
+                    }
+                    last = x;
+                }
+            }
+            ':' if chars.peek().is_some_and(|x| x.is_alphabetic()) => {
+                let mut name = String::new();
+                while let Some(x) = chars.next_if(|x| x.is_alphanumeric() || "_$#".contains(*x)) {
+                    name.push(x);
+                }
+                if chars.peek() != Some(&'.') {
+                    variables.push(name.to_uppercase());
+                }
+            }
+            _ => {}
+        }
+    }
+    variables
+}
+
+#[cfg(test)]
+mod test {
+    use super::{find_bind_variables, is_number, parse_variable_definition, VariableType};
+
+    #[test]
+    fn parse_definition() {
+        let var = parse_variable_definition("x number").unwrap();
+        assert_eq!(var.name, "X");
+        assert_eq!(var.type_, VariableType::Number);
+        assert_eq!(var.value, None);
+
+        let var = parse_variable_definition("name varchar2 (20 char) = 'it''s'").unwrap();
+        assert_eq!(var.type_, VariableType::VarChar(20));
+        assert_eq!(var.value.as_deref(), Some("it's"));
+
+        assert_eq!(
+            parse_variable_definition("c char").unwrap().type_,
+            VariableType::Char(1)
+        );
+        assert!(parse_variable_definition("x").is_err());
+        assert!(parse_variable_definition("1x number").is_err());
+        assert!(parse_variable_definition("x blob").is_err());
+        assert!(parse_variable_definition("x number = abc").is_err());
+        assert!(parse_variable_definition("x number = inf").is_err());
+        assert!(parse_variable_definition("x number = NaN").is_err());
+        assert!(parse_variable_definition("x char(2) = 'abc'").is_err());
+    }
+
+    #[test]
+    fn numbers() {
+        for number in [
+            "1",
+            "-1",
+            "+1.5",
+            ".5",
+            "1.",
+            "6.02E23",
+            "1e-3",
+            "12345678901234567890",
+        ] {
+            assert!(is_number(number), "{number}");
+        }
+        for number in [
+            "", "-", ".", "e1", "1e", "1e+", "1.2.3", "inf", "infinity", "NaN", "1 2",
+        ] {
+            assert!(!is_number(number), "{number}");
+        }
+    }
+
+    #[test]
+    fn find_variables() {
+        assert_eq!(
+            find_bind_variables("select * from t where a = :a and b = ':b' -- :c\n and d = :d"),
+            vec!["A", "D"]
+        );
+        assert_eq!(
+            find_bind_variables("begin x := 1; p(:out_1, \":q\"); /* :e */ end;"),
+            vec!["OUT_1"]
+        );
+        assert_eq!(
+            find_bind_variables("begin :new.id := :next_id; :old.x := 1; end;"),
+            vec!["NEXT_ID"]
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use thiserror::Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCondition {
+    /// Error occurs when executing SQL.
+    SqlError,
+    /// Error occurs when executing shell command.
+    OsError,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transaction {
+    Commit,
+    Rollback,
+    None,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitStatus {
+    Success,
+    Failure,
+    Warning,
+    Code(i32),
+    /// Exit with code of the error, `SQL.SQLCODE` or `OSCODE`.
+    ErrorCode,
+}
+
+impl ExitStatus {
+    pub fn code(&self, error_code: i32) -> i32 {
+        match self {
+            ExitStatus::Success => 0,
+            ExitStatus::Failure => 1,
+            ExitStatus::Warning => 2,
+            ExitStatus::Code(code) => *code,
+            ExitStatus::ErrorCode => error_code,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorAction {
+    Exit(ExitStatus, Transaction),
+    Continue(Transaction),
+}
+
+impl Default for ErrorAction {
+    fn default() -> Self {
+        Self::Continue(Transaction::None)
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum WheneverParsingError {
+    #[error("Expected SQLERROR or OSERROR")]
+    Condition,
+
+    #[error("Expected EXIT or CONTINUE")]
+    Action,
+
+    #[error("Invalid option: {0}")]
+    Option(String),
+}
+
+/// Parses `{SQLERROR|OSERROR} {EXIT [status] [COMMIT|ROLLBACK] | CONTINUE [COMMIT|ROLLBACK|NONE]}`.
+pub fn parse_whenever(
+    whenever: &str,
+) -> Result<(ErrorCondition, ErrorAction), WheneverParsingError> {
+    let mut tokens = whenever.split_whitespace().map(|x| x.to_uppercase());
+    let condition = match tokens.next().as_deref() {
+        Some("SQLERROR") => ErrorCondition::SqlError,
+        Some("OSERROR") => ErrorCondition::OsError,
+        _ => return Err(WheneverParsingError::Condition),
+    };
+    let tokens = tokens.collect::<Vec<_>>();
+    let (action, options) = tokens.split_first().ok_or(WheneverParsingError::Action)?;
+
+    let transaction = |option: Option<&String>, default| match option.map(|x| x.as_str()) {
+        None => Ok(default),
+        Some("COMMIT") => Ok(Transaction::Commit),
+        Some("ROLLBACK") => Ok(Transaction::Rollback),
+        Some("NONE") if matches!(action.as_str(), "CONTINUE") => Ok(Transaction::None),
+        Some(option) => Err(WheneverParsingError::Option(option.to_string())),
+    };
+    let action = match action.as_str() {
+        "EXIT" => {
+            let (status, options) = match options.split_first() {
+                Some((status, rest)) => match (status.as_str(), condition) {
+                    ("SUCCESS", _) => (Some(ExitStatus::Success), rest),
+                    ("FAILURE", _) => (Some(ExitStatus::Failure), rest),
+                    ("WARNING", _) => (Some(ExitStatus::Warning), rest),
+                    ("SQL.SQLCODE", ErrorCondition::SqlError)
+                    | ("OSCODE", ErrorCondition::OsError) => (Some(ExitStatus::ErrorCode), rest),
+                    (code, _) => match code.parse() {
+                        Ok(code) => (Some(ExitStatus::Code(code)), rest),
+                        Err(_) => (None, options),
+                    },
+                },
+                None => (None, options),
+            };
+            if options.len() > 1 {
+                return Err(WheneverParsingError::Option(options[1..].join(" ")));
+            }
+            ErrorAction::Exit(
+                status.unwrap_or(ExitStatus::Failure),
+                transaction(options.first(), Transaction::Commit)?,
+            )
+        }
+        "CONTINUE" => {
+            if options.len() > 1 {
+                return Err(WheneverParsingError::Option(options[1..].join(" ")));
+            }
+            ErrorAction::Continue(transaction(options.first(), Transaction::None)?)
+        }
+        _ => return Err(WheneverParsingError::Action),
+    };
+
+    Ok((condition, action))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        parse_whenever, ErrorAction, ErrorCondition, ExitStatus, Transaction, WheneverParsingError,
+    };
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            parse_whenever("sqlerror exit"),
+            Ok((
+                ErrorCondition::SqlError,
+                ErrorAction::Exit(ExitStatus::Failure, Transaction::Commit)
+            ))
+        );
+        assert_eq!(
+            parse_whenever("SQLERROR EXIT SQL.SQLCODE ROLLBACK"),
+            Ok((
+                ErrorCondition::SqlError,
+                ErrorAction::Exit(ExitStatus::ErrorCode, Transaction::Rollback)
+            ))
+        );
+        assert_eq!(
+            parse_whenever("oserror exit 3"),
+            Ok((
+                ErrorCondition::OsError,
+                ErrorAction::Exit(ExitStatus::Code(3), Transaction::Commit)
+            ))
+        );
+        assert_eq!(
+            parse_whenever("oserror continue none"),
+            Ok((
+                ErrorCondition::OsError,
+                ErrorAction::Continue(Transaction::None)
+            ))
+        );
+
+        assert_eq!(
+            parse_whenever("error exit"),
+            Err(WheneverParsingError::Condition)
+        );
+        assert_eq!(
+            parse_whenever("sqlerror"),
+            Err(WheneverParsingError::Action)
+        );
+        assert_eq!(
+            parse_whenever("oserror exit sql.sqlcode"),
+            Err(WheneverParsingError::Option("SQL.SQLCODE".to_string()))
+        );
+        assert_eq!(
+            parse_whenever("sqlerror exit none"),
+            Err(WheneverParsingError::Option("NONE".to_string()))
+        );
+    }
+}
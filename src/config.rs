@@ -0,0 +1,323 @@
+use std::{
+    collections::BTreeMap,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use rustyline::KeyEvent;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::app::input::{parse_keys, Action, KeymapError};
+use crate::command::{parse_set, Connection, SetParsingError, Setting};
+
+/// Startup settings, from `~/.config/yasqlplus/config.toml` by default.
+///
+/// ```toml
+/// prompt = "{username}@{host} > "
+/// history = "~/.yasqlplus-history.txt"
+///
+/// [set]
+/// markup = "text"
+/// pagesize = 50
+/// timing = true
+/// pager = "less -RS"
+/// theme = "Solarized (dark)"
+/// editmode = "emacs"
+///
+/// [keys]
+/// "alt-enter" = "execute-buffer"
+/// "ctrl-f" = "format-statement"
+///
+/// # `conn @prod` or `yasqlplus @prod`
+/// [connections.prod]
+/// host = "10.0.0.1"
+/// port = 1688
+/// username = "app"
+/// schema = "sales"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Prompt when connected, `{session}`, `{username}`, `{host}` and `{port}` will be replaced.
+    pub prompt: Option<String>,
+
+    /// Path of the history file.
+    pub history: Option<PathBuf>,
+
+    /// Same as `SET`, `true`/`false` for `ON`/`OFF`.
+    pub set: BTreeMap<String, toml::Value>,
+
+    /// Named connection profiles.
+    pub connections: BTreeMap<String, Profile>,
+
+    /// Actions of keys like `"alt-enter" = "execute-buffer"`, or sequences like `"ctrl-x e"`.
+    pub keys: BTreeMap<String, String>,
+
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+/// Connection profile referred like a host, e.g. `sys@prod`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Enabled by `SET ROLE` once connected.
+    pub role: Option<String>,
+    /// Set as `CURRENT_SCHEMA` once connected.
+    pub schema: Option<String>,
+    /// Set by `ALTER SESSION` once connected.
+    pub options: BTreeMap<String, String>,
+}
+
+impl Profile {
+    /// Statements to initialize the session.
+    pub fn init_statements(&self) -> Vec<String> {
+        let role = self.role.iter().map(|role| format!("SET ROLE {role}"));
+        let schema = self
+            .schema
+            .iter()
+            .map(|schema| format!("ALTER SESSION SET CURRENT_SCHEMA = {schema}"));
+        let options = self
+            .options
+            .iter()
+            .map(|(option, value)| format!("ALTER SESSION SET {option} = {value}"));
+        role.chain(schema).chain(options).collect()
+    }
+}
+
+/// Fills absent fields of `connection` from the profile if its host names one, then `env`,
+/// e.g. from `YASHANDB_*` environment variables.
+pub fn resolve_connection(
+    connection: &Connection,
+    profiles: &BTreeMap<String, Profile>,
+    env: &Connection,
+) -> (Connection, Option<Profile>) {
+    let profile = connection
+        .host
+        .as_ref()
+        .filter(|_| connection.port.is_none())
+        .and_then(|host| profiles.get(host))
+        .cloned();
+    let selected = profile.clone().unwrap_or_default();
+    let host = match profile {
+        Some(_) => None,
+        None => connection.host.clone(),
+    };
+    let env = env.clone();
+    let connection = Connection {
+        host: host.or(selected.host).or(env.host),
+        port: connection.port.or(selected.port).or(env.port),
+        username: connection
+            .username
+            .clone()
+            .or(selected.username)
+            .or(env.username),
+        password: connection
+            .password
+            .clone()
+            .or(selected.password)
+            .or(env.password),
+    };
+    (connection, profile)
+}
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("Failed to read {path}: {err}")]
+    Io { path: String, err: std::io::Error },
+
+    #[error("Failed to parse {path}: {err}")]
+    Toml { path: String, err: toml::de::Error },
+
+    #[error("Invalid setting in {path}: {err}")]
+    Set { path: String, err: SetParsingError },
+
+    #[error("Invalid key binding in {path}: {err}")]
+    Key { path: String, err: KeymapError },
+}
+
+/// Directory of `config.toml` and `login.sql`.
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|x| x.join("yasqlplus"))
+}
+
+/// Replaces leading `~` with the home directory.
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(path), Some(home)) => home.join(path),
+        _ => path.to_path_buf(),
+    }
+}
+
+impl Config {
+    /// Loads from `path`, or from the default location if exists.
+    pub fn load(path: Option<&Path>) -> Result<Self, ConfigError> {
+        let (path, optional) = match path {
+            Some(path) => (path.to_path_buf(), false),
+            None => match config_dir() {
+                Some(dir) => (dir.join("config.toml"), true),
+                None => return Ok(Self::default()),
+            },
+        };
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if optional && err.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => {
+                return Err(ConfigError::Io {
+                    path: path.display().to_string(),
+                    err,
+                })
+            }
+        };
+        let mut config: Self = toml::from_str(&content).map_err(|err| ConfigError::Toml {
+            path: path.display().to_string(),
+            err,
+        })?;
+        config.history = config.history.as_deref().map(expand_home);
+        config.path = path;
+        Ok(config)
+    }
+
+    /// Settings in `[set]` parsed like `SET` commands.
+    pub fn settings(&self) -> Result<Vec<Setting>, ConfigError> {
+        self.set
+            .iter()
+            .map(|(option, value)| {
+                let value = match value {
+                    toml::Value::String(value) => value.clone(),
+                    toml::Value::Boolean(value) => if *value { "ON" } else { "OFF" }.to_string(),
+                    value => value.to_string(),
+                };
+                parse_set(&format!("{option} {value}")).map_err(|err| ConfigError::Set {
+                    path: self.path.display().to_string(),
+                    err,
+                })
+            })
+            .collect()
+    }
+
+    /// Bindings in `[keys]`.
+    pub fn keys(&self) -> Result<Vec<(Vec<KeyEvent>, Action)>, ConfigError> {
+        self.keys
+            .iter()
+            .map(|(keys, action)| Ok((parse_keys(keys)?, action.parse()?)))
+            .collect::<Result<_, KeymapError>>()
+            .map_err(|err| ConfigError::Key {
+                path: self.path.display().to_string(),
+                err,
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rustyline::{KeyCode, KeyEvent, Modifiers};
+
+    use crate::app::input::Action;
+    use crate::command::{parse_connection_string, Connection, PagerMode, ResultFormat, Setting};
+
+    use super::{resolve_connection, Config};
+
+    #[test]
+    fn settings() {
+        let config: Config = toml::from_str(
+            r#"
+prompt = "{username} > "
+
+[set]
+markup = "text"
+pagesize = 50
+timing = true
+pager = "never"
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.prompt.as_deref(), Some("{username} > "));
+        assert_eq!(
+            config.settings().unwrap(),
+            vec![
+                Setting::Markup(ResultFormat::Text),
+                Setting::Pager(PagerMode::Never),
+                Setting::PageSize(50),
+                Setting::Timing(true),
+            ]
+        );
+
+        let config: Config = toml::from_str(
+            r#"
+[keys]
+"alt-enter" = "execute-buffer"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.keys().unwrap(),
+            vec![(
+                vec![KeyEvent(KeyCode::Enter, Modifiers::ALT)],
+                Action::ExecuteBuffer
+            )]
+        );
+        let config: Config = toml::from_str("[keys]\nf5 = \"run\"").unwrap();
+        assert!(config.keys().is_err());
+
+        let config: Config = toml::from_str("[set]\npagesize = -1").unwrap();
+        assert!(config.settings().is_err());
+        assert!(toml::from_str::<Config>("unknown = 1").is_err());
+    }
+
+    #[test]
+    fn profiles() {
+        let config: Config = toml::from_str(
+            r#"
+[connections.prod]
+host = "10.0.0.1"
+username = "app"
+schema = "sales"
+options = { nls_date_format = "'YYYY-MM-DD'" }
+"#,
+        )
+        .unwrap();
+        let profile = &config.connections["prod"];
+        assert_eq!(
+            profile.init_statements(),
+            vec![
+                "ALTER SESSION SET CURRENT_SCHEMA = sales",
+                "ALTER SESSION SET nls_date_format = 'YYYY-MM-DD'",
+            ]
+        );
+
+        let env = Connection {
+            host: Some("192.168.0.1".to_string()),
+            port: Some(1688),
+            username: Some("sys".to_string()),
+            password: None,
+        };
+        let resolve = |conn: &str| {
+            resolve_connection(
+                &parse_connection_string(conn).unwrap(),
+                &config.connections,
+                &env,
+            )
+        };
+        let (connection, resolved) = resolve("/pwd@prod");
+        assert_eq!(resolved.as_ref(), Some(profile));
+        assert_eq!(connection.host.as_deref(), Some("10.0.0.1"));
+        assert_eq!(connection.port, Some(1688));
+        assert_eq!(connection.username.as_deref(), Some("app"));
+        assert_eq!(connection.password.as_deref(), Some("pwd"));
+
+        let (connection, _) = resolve("admin@prod");
+        assert_eq!(connection.username.as_deref(), Some("admin"));
+        assert_eq!(connection.password, None);
+
+        // a host
+        let (connection, resolved) = resolve("sys@prod:1688");
+        assert_eq!(resolved, None);
+        assert_eq!(connection.host.as_deref(), Some("prod"));
+    }
+}
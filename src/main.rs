@@ -1,4 +1,4 @@
-use std::{fs::File, io::BufReader, path::PathBuf, rc::Rc, sync::RwLock};
+use std::{fs::File, io::BufReader, path::PathBuf, process::ExitCode, rc::Rc, sync::RwLock};
 
 use app::{
     context::Context,
@@ -6,10 +6,16 @@ use app::{
     AppError,
 };
 use clap::Parser;
-use command::{parse_connection_string, Command, Connection, InternalCommand};
+use command::{
+    parse_connection_string, Command, Connection, InternalCommand, PagerMode, ResultFormat,
+};
+use config::{config_dir, Config};
+use yasqlplus_client::wrapper::get_connect_info;
 
 mod app;
 mod command;
+mod config;
+mod passfile;
 
 #[derive(Debug, Parser)]
 #[command(author, version, about, long_about=None)]
@@ -18,6 +24,10 @@ struct Cli {
     ///
     ///     [<username>][/[<password>][@[<host>][:[<port>]]]]
     ///
+    /// <host> could be name of a profile in config file, like `@prod`.
+    /// Absent parts will be read from `YASHANDB_HOST`, `YASHANDB_PORT`,
+    /// `YASHANDB_USERNAME` and `YASHANDB_PASSWORD`, then the profile.
+    ///
     /// <host> will default to `127.0.0.1`.
     /// <port> will default to `1688`.
     ///
@@ -28,6 +38,7 @@ struct Cli {
     /// For example:
     ///     sys/yasdb_123@127.0.0.1:1688
     ///     sys/yasdb_123@:1601
+    ///     sys@prod
     #[arg(verbatim_doc_comment)]
     conn: Vec<String>,
 
@@ -59,7 +70,15 @@ struct Cli {
     #[arg(short, long)]
     command: Option<String>,
 
-    /// Disable show large table in less.
+    /// Format of query result, same as `SET MARKUP`.
+    ///
+    /// table, csv, tsv, json, ndjson, markdown, html or text.
+    /// For example:
+    ///     --format "csv delimiter ; quote off"
+    #[arg(long, verbatim_doc_comment)]
+    format: Option<ResultFormat>,
+
+    /// Never show large table in pager, same as `SET PAGER NEVER`.
     #[arg(long)]
     no_less: bool,
 
@@ -71,14 +90,36 @@ struct Cli {
     /// History file name.
     #[arg(short = 'F', long, default_value = "yasqlplus-history.txt")]
     history_file: String,
+
+    /// Config file. Default to `yasqlplus/config.toml` in user config directory.
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Don't run `login.sql` in user config directory and current directory.
+    #[arg(long)]
+    no_login: bool,
 }
 
-fn main() -> Result<(), AppError> {
+fn main() -> Result<ExitCode, AppError> {
     let args = Cli::parse();
+    let config = Config::load(args.config.as_deref())?;
+    let keys = config.keys()?;
 
     let mut ctx = Context::default();
+    for setting in config.settings()? {
+        ctx.apply_setting(&setting)?;
+    }
+    if let Some(prompt) = config.prompt {
+        ctx.set_prompt_template(prompt);
+    }
+    ctx.set_profiles(config.connections);
     ctx.set_need_echo(args.echo);
-    ctx.set_less_enabled(!args.no_less);
+    if args.no_less {
+        ctx.set_pager_mode(PagerMode::Never);
+    }
+    if let Some(format) = args.format {
+        ctx.set_result_format(format);
+    }
 
     let ctx = Rc::new(RwLock::new(ctx));
     let input: Box<dyn Input> = match args.command {
@@ -88,13 +129,15 @@ fn main() -> Result<(), AppError> {
             Some(input) => Box::new(BufReaderInput::new(BufReader::new(File::open(input)?))),
             None => Box::new(ShellInput::new(
                 ctx.clone(),
-                args.history_path
-                    .map(PathBuf::from)
-                    .unwrap_or_else(|| dirs::home_dir().unwrap_or_default())
-                    .join(args.history_file)
-                    .to_str()
-                    .unwrap()
-                    .to_owned(),
+                match (args.history_path, config.history) {
+                    (Some(path), _) => PathBuf::from(path).join(args.history_file),
+                    (None, Some(history)) => history,
+                    (None, None) => dirs::home_dir().unwrap_or_default().join(args.history_file),
+                }
+                .to_str()
+                .unwrap()
+                .to_owned(),
+                keys,
             )?),
         },
     };
@@ -110,12 +153,49 @@ fn main() -> Result<(), AppError> {
         host: args.host.or(connection.host),
         port: args.port.or(connection.port),
     };
-    if connection.any_valid() {
+    if connection.any_valid() || Connection::from(get_connect_info()).any_valid() {
         let _ = app.step(Some((
-            Command::Internal(InternalCommand::Connect(connection)),
+            Command::Internal(InternalCommand::Connect(None, connection)),
             conn_str,
         )));
     }
 
-    app.run()
+    if !args.no_login {
+        let scripts = [
+            config_dir().map(|x| x.join("login.sql")),
+            Some(PathBuf::from("login.sql")),
+        ];
+        for script in scripts.iter().flatten().filter(|x| x.is_file()) {
+            if let Some(code) = app.run_script(script)? {
+                return Ok(ExitCode::from(exit_status(code)));
+            }
+        }
+    }
+
+    let code = app.run()?;
+    Ok(ExitCode::from(exit_status(code)))
+}
+
+/// Exit status of the process, only the low 8 bits of `code` are kept, but failures stay nonzero.
+fn exit_status(code: i32) -> u8 {
+    if code != 0 && code & 0xff == 0 {
+        1
+    } else {
+        code as u8
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::exit_status;
+
+    #[test]
+    fn status() {
+        assert_eq!(exit_status(0), 0);
+        assert_eq!(exit_status(3), 3);
+        assert_eq!(exit_status(1280), 1);
+        assert_eq!(exit_status(256), 1);
+        assert_eq!(exit_status(257), 1);
+        assert_eq!(exit_status(-1), 255);
+    }
 }
@@ -0,0 +1,107 @@
+use std::{io::ErrorKind, path::PathBuf};
+
+use thiserror::Error;
+
+use crate::config::config_dir;
+
+#[derive(Debug, Error)]
+pub enum PassfileError {
+    #[error("Failed to read {path}: {err}")]
+    Io { path: String, err: std::io::Error },
+
+    #[error("Ignored {path} which is accessible by group or others, try `chmod 600 {path}`")]
+    Permissions { path: String },
+}
+
+/// `$YASQLPLUS_PASSFILE`, or `passfile` in user config directory.
+pub fn passfile_path() -> Option<PathBuf> {
+    std::env::var_os("YASQLPLUS_PASSFILE")
+        .map(PathBuf::from)
+        .or_else(|| config_dir().map(|x| x.join("passfile")))
+}
+
+/// Finds password in the passfile, which is like `.pgpass` of PostgreSQL.
+///
+/// Each line is `host:port:username:password`, `*` matches anything in the first three fields,
+/// `\` escapes `:` and `\`. Lines starting with `#` are comments.
+pub fn find_password(
+    host: &str,
+    port: u16,
+    username: &str,
+) -> Result<Option<String>, PassfileError> {
+    let Some(path) = passfile_path() else {
+        return Ok(None);
+    };
+    let io_error = |err| PassfileError::Io {
+        path: path.display().to_string(),
+        err,
+    };
+    let metadata = match std::fs::metadata(&path) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(io_error(err)),
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if metadata.permissions().mode() & 0o077 != 0 {
+            return Err(PassfileError::Permissions {
+                path: path.display().to_string(),
+            });
+        }
+    }
+    let content = std::fs::read_to_string(&path).map_err(io_error)?;
+    Ok(lookup(&content, host, port, username))
+}
+
+fn lookup(content: &str, host: &str, port: u16, username: &str) -> Option<String> {
+    let port = port.to_string();
+    content
+        .lines()
+        .filter(|x| !x.trim_start().starts_with('#'))
+        .map(split_fields)
+        .find_map(|fields| match fields.as_slice() {
+            [h, p, u, password]
+                if [(h, host), (p, port.as_str()), (u, username)]
+                    .iter()
+                    .all(|(field, value)| *field == "*" || field == value) =>
+            {
+                Some(password.clone())
+            }
+            _ => None,
+        })
+}
+
+/// Splits by `:` except escaped ones.
+fn split_fields(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut chars = line.chars();
+    while let Some(x) = chars.next() {
+        match x {
+            '\\' => fields.last_mut().unwrap().extend(chars.next()),
+            ':' => fields.push(String::new()),
+            x => fields.last_mut().unwrap().push(x),
+        }
+    }
+    fields
+}
+
+#[cfg(test)]
+mod test {
+    use super::lookup;
+
+    #[test]
+    fn passfile() {
+        let content = r"
+# comment
+10.0.0.1:1688:sys:sys\:pwd
+*:*:app:app_pwd\\
+127.0.0.1:*:*:local
+";
+        let find = |host, port, username| lookup(content, host, port, username);
+        assert_eq!(find("10.0.0.1", 1688, "sys"), Some("sys:pwd".to_string()));
+        assert_eq!(find("10.0.0.1", 1601, "app"), Some("app_pwd\\".to_string()));
+        assert_eq!(find("127.0.0.1", 1688, "sys"), Some("local".to_string()));
+        assert_eq!(find("10.0.0.1", 1601, "sys"), None);
+    }
+}
@@ -0,0 +1,762 @@
+//! Typed queries on the data dictionary.
+
+use crate::wrapper::{Connection, Error, ParamDirection, Parameter, Type};
+
+/// Which data dictionary views to query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Views {
+    /// `ALL_*`, objects accessible to the current user.
+    All,
+    /// `DBA_*`, all objects, which requires privileges.
+    Dba,
+}
+
+impl Views {
+    fn prefix(self) -> &'static str {
+        match self {
+            Views::All => "all",
+            Views::Dba => "dba",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Object {
+    pub owner: String,
+    pub name: String,
+    /// e.g. `TABLE`, `PACKAGE`.
+    pub type_: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableColumn {
+    pub name: String,
+    /// e.g. `VARCHAR(20)`, `NUMBER(10,2)`.
+    pub data_type: String,
+    pub nullable: bool,
+    pub default: Option<String>,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConstraintType {
+    PrimaryKey,
+    Unique,
+    ForeignKey,
+    Check,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constraint {
+    pub name: String,
+    pub type_: ConstraintType,
+    pub columns: Vec<String>,
+    /// Table, as `owner.table`, and columns referenced by a foreign key.
+    pub references: Option<(String, Vec<String>)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Index {
+    pub owner: String,
+    pub name: String,
+    pub unique: bool,
+    pub columns: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sequence {
+    pub owner: String,
+    pub name: String,
+    pub min_value: String,
+    pub max_value: String,
+    pub increment_by: String,
+    pub cycle: bool,
+    pub cache_size: String,
+    pub last_number: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Synonym {
+    pub owner: String,
+    pub name: String,
+    pub table_owner: String,
+    pub table_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Argument {
+    /// Name of the procedure or function.
+    pub subprogram: String,
+    /// Tells overloaded subprograms in a package apart.
+    pub subprogram_id: String,
+    /// `None` for the return value of a function, or a subprogram without arguments.
+    pub name: Option<String>,
+    /// `0` for the return value.
+    pub position: usize,
+    pub data_type: Option<String>,
+    pub in_out: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trigger {
+    pub owner: String,
+    pub name: String,
+    pub table_owner: String,
+    pub table_name: String,
+    /// e.g. `BEFORE EACH ROW`.
+    pub trigger_type: String,
+    /// e.g. `INSERT OR UPDATE`.
+    pub event: String,
+    pub enabled: bool,
+}
+
+/// Runs queries on the data dictionary, a connection or rows made up in tests.
+pub trait Source {
+    /// Runs the query with parameters bound in order, values as text.
+    fn query(&self, sql: &str, parameters: &[&str]) -> Result<Vec<Vec<Option<String>>>, Error>;
+}
+
+impl Source for Connection {
+    fn query(&self, sql: &str, parameters: &[&str]) -> Result<Vec<Vec<Option<String>>>, Error> {
+        let mut statement = self.create_statement()?.prepare(sql)?;
+        for parameter in parameters {
+            statement.bind_parameter(Parameter::new(
+                ParamDirection::Input,
+                Type::VarChar,
+                parameter.len(),
+                Some(parameter),
+            ));
+        }
+        let (executed, _) = statement.execute()?;
+        Ok(executed
+            .result_set()
+            .rows()
+            .map(|row| row.into_iter().map(|x| x.map(|x| x.to_string())).collect())
+            .collect())
+    }
+}
+
+/// Lists objects of the database, through `DBA_*` views if readable, `ALL_*` views otherwise.
+///
+/// Functions taking `owner: Option<&str>` list objects of all schemas if `None`.
+pub struct Catalog<'a, S: Source = Connection> {
+    source: &'a S,
+    views: Views,
+}
+
+impl<'a, S: Source> Catalog<'a, S> {
+    /// Picks `DBA_*` views if the current user could read them.
+    pub fn new(source: &'a S) -> Self {
+        let views = match source.query("select 1 from dba_objects where 1 = 0", &[]) {
+            Ok(_) => Views::Dba,
+            Err(_) => Views::All,
+        };
+        Self::with_views(source, views)
+    }
+
+    pub fn with_views(source: &'a S, views: Views) -> Self {
+        Self { source, views }
+    }
+
+    pub fn views_used(&self) -> Views {
+        self.views
+    }
+
+    pub fn current_schema(&self) -> Result<String, Error> {
+        let rows = self.query(
+            "select sys_context('USERENV', 'CURRENT_SCHEMA') from dual",
+            &[],
+        )?;
+        Ok(rows
+            .into_iter()
+            .next()
+            .and_then(|mut x| x.remove(0))
+            .unwrap_or_default())
+    }
+
+    pub fn schemas(&self) -> Result<Vec<String>, Error> {
+        let sql = format!(
+            "select username from {}_users order by username",
+            self.prefix()
+        );
+        Ok(self
+            .query(&sql, &[])?
+            .into_iter()
+            .filter_map(|mut x| x.remove(0))
+            .collect())
+    }
+
+    /// Objects of the types, e.g. `&["PROCEDURE", "FUNCTION"]`.
+    pub fn objects(&self, owner: Option<&str>, types: &[&str]) -> Result<Vec<Object>, Error> {
+        let types = types
+            .iter()
+            .map(|x| format!("'{}'", x.replace('\'', "''")))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let sql = format!(
+            "select owner, object_name, object_type from {}_objects where object_type in ({types}){} \
+             order by owner, object_name",
+            self.prefix(),
+            owner_filter("owner", owner),
+        );
+        self.query(&sql, owner.as_slice())?
+            .into_iter()
+            .map(|row| {
+                let [owner, name, type_] = columns(row)?;
+                Ok(Object { owner, name, type_ })
+            })
+            .collect()
+    }
+
+    pub fn tables(&self, owner: Option<&str>) -> Result<Vec<Object>, Error> {
+        self.objects(owner, &["TABLE"])
+    }
+
+    pub fn views(&self, owner: Option<&str>) -> Result<Vec<Object>, Error> {
+        self.objects(owner, &["VIEW"])
+    }
+
+    /// Standalone procedures and functions, and packages.
+    pub fn procedures(&self, owner: Option<&str>) -> Result<Vec<Object>, Error> {
+        self.objects(owner, &["PROCEDURE", "FUNCTION", "PACKAGE"])
+    }
+
+    /// Finds the object by name, except package bodies.
+    ///
+    /// Without owner, it's looked up in the current schema, then public synonyms.
+    pub fn find_object(&self, owner: Option<&str>, name: &str) -> Result<Option<Object>, Error> {
+        let (condition, parameters) = match owner {
+            Some(owner) => ("owner = :owner", vec![owner, name]),
+            None => (
+                "owner in (sys_context('USERENV', 'CURRENT_SCHEMA'), 'PUBLIC')",
+                vec![name],
+            ),
+        };
+        let sql = format!(
+            "select owner, object_name, object_type from {}_objects \
+             where {condition} and object_name = :name \
+             and object_type not in ('PACKAGE BODY', 'INDEX', 'LOB') \
+             order by decode(owner, 'PUBLIC', 1, 0)",
+            self.prefix()
+        );
+        self.query(&sql, &parameters)?
+            .into_iter()
+            .next()
+            .map(|row| {
+                let [owner, name, type_] = columns(row)?;
+                Ok(Object { owner, name, type_ })
+            })
+            .transpose()
+    }
+
+    /// Comment of the table or view.
+    pub fn table_comment(&self, owner: &str, table: &str) -> Result<Option<String>, Error> {
+        let sql = format!(
+            "select comments from {}_tab_comments where owner = :owner and table_name = :name",
+            self.prefix()
+        );
+        Ok(self
+            .query(&sql, &[owner, table])?
+            .into_iter()
+            .find_map(|mut x| x.remove(0)))
+    }
+
+    /// Columns of the table or view in order.
+    pub fn columns(&self, owner: &str, table: &str) -> Result<Vec<TableColumn>, Error> {
+        let sql = format!(
+            "select c.column_name, c.data_type, c.data_length, c.data_precision, c.data_scale, \
+             c.nullable, c.data_default, m.comments \
+             from {p}_tab_columns c left join {p}_col_comments m \
+             on m.owner = c.owner and m.table_name = c.table_name \
+             and m.column_name = c.column_name \
+             where c.owner = :owner and c.table_name = :name \
+             order by c.column_id",
+            p = self.prefix()
+        );
+        self.query(&sql, &[owner, table])?
+            .into_iter()
+            .map(|row| {
+                let [name, type_, length, precision, scale, nullable, default, comment] =
+                    nullable_columns(row)?;
+                Ok(TableColumn {
+                    name: name.unwrap_or_default(),
+                    data_type: data_type(
+                        &type_.unwrap_or_default(),
+                        length.as_deref(),
+                        precision.as_deref(),
+                        scale.as_deref(),
+                    ),
+                    nullable: nullable.as_deref() != Some("N"),
+                    default: default.map(|x| x.trim().to_string()),
+                    comment,
+                })
+            })
+            .collect()
+    }
+
+    /// Primary key, unique, foreign key and check constraints of the table.
+    pub fn constraints(&self, owner: &str, table: &str) -> Result<Vec<Constraint>, Error> {
+        // columns referenced by a foreign key are in the same positions as its columns
+        let sql = format!(
+            "select c.constraint_name, c.constraint_type, c.r_owner, r.table_name, \
+             k.column_name, r.column_name \
+             from {p}_constraints c left join {p}_cons_columns k \
+             on k.owner = c.owner and k.constraint_name = c.constraint_name \
+             left join {p}_cons_columns r \
+             on r.owner = c.r_owner and r.constraint_name = c.r_constraint_name \
+             and r.position = k.position \
+             where c.owner = :owner and c.table_name = :name \
+             and c.constraint_type in ('P', 'U', 'R', 'C') \
+             order by decode(c.constraint_type, 'P', 0, 'U', 1, 'R', 2, 3), \
+             c.constraint_name, k.position",
+            p = self.prefix()
+        );
+        let mut constraints: Vec<Constraint> = vec![];
+        for row in self.query(&sql, &[owner, table])? {
+            let [name, type_, r_owner, r_table, column, r_column] = nullable_columns(row)?;
+            let name = name.unwrap_or_default();
+            let constraint = match constraints.last_mut() {
+                Some(constraint) if constraint.name == name => constraint,
+                _ => {
+                    constraints.push(Constraint {
+                        name,
+                        type_: match type_.as_deref() {
+                            Some("P") => ConstraintType::PrimaryKey,
+                            Some("U") => ConstraintType::Unique,
+                            Some("R") => ConstraintType::ForeignKey,
+                            _ => ConstraintType::Check,
+                        },
+                        columns: vec![],
+                        references: r_owner
+                            .zip(r_table)
+                            .map(|(owner, table)| (format!("{owner}.{table}"), vec![])),
+                    });
+                    constraints.last_mut().unwrap()
+                }
+            };
+            constraint.columns.extend(column);
+            if let Some((_, columns)) = &mut constraint.references {
+                columns.extend(r_column);
+            }
+        }
+        Ok(constraints)
+    }
+
+    /// Indexes on the table.
+    pub fn indexes(&self, owner: &str, table: &str) -> Result<Vec<Index>, Error> {
+        let sql = format!(
+            "select i.owner, i.index_name, i.uniqueness, c.column_name \
+             from {p}_indexes i join {p}_ind_columns c \
+             on c.index_owner = i.owner and c.index_name = i.index_name \
+             where i.table_owner = :owner and i.table_name = :name \
+             order by i.owner, i.index_name, c.column_position",
+            p = self.prefix()
+        );
+        let mut indexes: Vec<Index> = vec![];
+        for row in self.query(&sql, &[owner, table])? {
+            let [owner, name, uniqueness, column] = nullable_columns(row)?;
+            let (owner, name) = (owner.unwrap_or_default(), name.unwrap_or_default());
+            match indexes.last_mut() {
+                Some(index) if index.owner == owner && index.name == name => {
+                    index.columns.extend(column)
+                }
+                _ => indexes.push(Index {
+                    owner,
+                    name,
+                    unique: uniqueness.as_deref() == Some("UNIQUE"),
+                    columns: column.into_iter().collect(),
+                }),
+            }
+        }
+        Ok(indexes)
+    }
+
+    pub fn sequences(&self, owner: Option<&str>) -> Result<Vec<Sequence>, Error> {
+        self.query_sequences(owner_filter("sequence_owner", owner), owner.as_slice())
+    }
+
+    pub fn sequence(&self, owner: &str, name: &str) -> Result<Option<Sequence>, Error> {
+        Ok(self
+            .query_sequences(
+                " and sequence_owner = :owner and sequence_name = :name".to_string(),
+                &[owner, name],
+            )?
+            .into_iter()
+            .next())
+    }
+
+    fn query_sequences(&self, filter: String, parameters: &[&str]) -> Result<Vec<Sequence>, Error> {
+        let sql = format!(
+            "select sequence_owner, sequence_name, min_value, max_value, increment_by, \
+             cycle_flag, cache_size, last_number from {}_sequences where 1 = 1{filter} \
+             order by sequence_owner, sequence_name",
+            self.prefix()
+        );
+        self.query(&sql, parameters)?
+            .into_iter()
+            .map(|row| {
+                let [owner, name, min_value, max_value, increment_by, cycle, cache_size, last_number] =
+                    columns(row)?;
+                Ok(Sequence {
+                    owner,
+                    name,
+                    min_value,
+                    max_value,
+                    increment_by,
+                    cycle: cycle == "Y",
+                    cache_size,
+                    last_number,
+                })
+            })
+            .collect()
+    }
+
+    pub fn synonyms(&self, owner: Option<&str>) -> Result<Vec<Synonym>, Error> {
+        self.query_synonyms(owner_filter("owner", owner), owner.as_slice())
+    }
+
+    pub fn synonym(&self, owner: &str, name: &str) -> Result<Option<Synonym>, Error> {
+        Ok(self
+            .query_synonyms(
+                " and owner = :owner and synonym_name = :name".to_string(),
+                &[owner, name],
+            )?
+            .into_iter()
+            .next())
+    }
+
+    fn query_synonyms(&self, filter: String, parameters: &[&str]) -> Result<Vec<Synonym>, Error> {
+        let sql = format!(
+            "select owner, synonym_name, table_owner, table_name from {}_synonyms \
+             where 1 = 1{filter} order by owner, synonym_name",
+            self.prefix()
+        );
+        self.query(&sql, parameters)?
+            .into_iter()
+            .map(|row| {
+                let [owner, name, table_owner, table_name] = columns(row)?;
+                Ok(Synonym {
+                    owner,
+                    name,
+                    table_owner,
+                    table_name,
+                })
+            })
+            .collect()
+    }
+
+    /// Arguments of a standalone procedure or function, or of all subprograms in a package,
+    /// ordered by subprogram and position.
+    pub fn arguments(
+        &self,
+        owner: &str,
+        name: &str,
+        package: bool,
+    ) -> Result<Vec<Argument>, Error> {
+        let condition = if package {
+            "package_name = :name"
+        } else {
+            "object_name = :name and package_name is null"
+        };
+        let sql = format!(
+            "select object_name, subprogram_id, argument_name, position, data_type, in_out \
+             from {}_arguments where owner = :owner and {condition} \
+             order by subprogram_id, position",
+            self.prefix()
+        );
+        self.query(&sql, &[owner, name])?
+            .into_iter()
+            .map(|row| {
+                let [subprogram, subprogram_id, name, position, data_type, in_out] =
+                    nullable_columns(row)?;
+                Ok(Argument {
+                    subprogram: subprogram.unwrap_or_default(),
+                    subprogram_id: subprogram_id.unwrap_or_default(),
+                    name,
+                    position: match position {
+                        Some(position) => position
+                            .parse()
+                            .map_err(|_| Error::Unexpected(format!("position {position}")))?,
+                        None => 0,
+                    },
+                    data_type,
+                    in_out: in_out.unwrap_or_default(),
+                })
+            })
+            .collect()
+    }
+
+    pub fn triggers(&self, owner: Option<&str>) -> Result<Vec<Trigger>, Error> {
+        let sql = format!(
+            "select owner, trigger_name, table_owner, table_name, trigger_type, \
+             triggering_event, status from {}_triggers where 1 = 1{} \
+             order by owner, trigger_name",
+            self.prefix(),
+            owner_filter("owner", owner),
+        );
+        self.query(&sql, owner.as_slice())?
+            .into_iter()
+            .map(|row| {
+                let [owner, name, table_owner, table_name, trigger_type, event, status] =
+                    columns(row)?;
+                Ok(Trigger {
+                    owner,
+                    name,
+                    table_owner,
+                    table_name,
+                    trigger_type,
+                    event,
+                    enabled: status == "ENABLED",
+                })
+            })
+            .collect()
+    }
+
+    fn prefix(&self) -> &'static str {
+        self.views.prefix()
+    }
+
+    fn query(&self, sql: &str, parameters: &[&str]) -> Result<Vec<Vec<Option<String>>>, Error> {
+        self.source.query(sql, parameters)
+    }
+}
+
+/// Condition on the owner column, bound as `:owner` if given.
+fn owner_filter(column: &str, owner: Option<&str>) -> String {
+    match owner {
+        Some(_) => format!(" and {column} = :owner"),
+        None => String::new(),
+    }
+}
+
+/// Takes `N` columns of the row, `NULL`s as empty strings.
+fn columns<const N: usize>(row: Vec<Option<String>>) -> Result<[String; N], Error> {
+    Ok(nullable_columns(row)?.map(Option::unwrap_or_default))
+}
+
+/// Takes `N` columns of the row, an error if it has another number of columns.
+fn nullable_columns<const N: usize>(
+    row: Vec<Option<String>>,
+) -> Result<[Option<String>; N], Error> {
+    let len = row.len();
+    row.try_into()
+        .map_err(|_| Error::Unexpected(format!("{len} columns, {N} expected")))
+}
+
+/// Splits `[schema.]name`, unquoted parts are upper-cased.
+pub fn split_name(name: &str) -> (Option<String>, String) {
+    let normalize = |x: &str| {
+        let x = x.trim();
+        match x.strip_prefix('"').and_then(|x| x.strip_suffix('"')) {
+            Some(quoted) => quoted.to_string(),
+            None => x.to_uppercase(),
+        }
+    };
+    // dots in quoted names are kept
+    let mut quoted = false;
+    let dot = name.char_indices().find_map(|(i, ch)| {
+        if ch == '"' {
+            quoted = !quoted;
+        }
+        (ch == '.' && !quoted).then_some(i)
+    });
+    match dot {
+        Some(dot) => (Some(normalize(&name[..dot])), normalize(&name[dot + 1..])),
+        None => (None, normalize(name)),
+    }
+}
+
+/// Formats like `VARCHAR(20)` or `NUMBER(10,2)`.
+fn data_type(
+    type_: &str,
+    length: Option<&str>,
+    precision: Option<&str>,
+    scale: Option<&str>,
+) -> String {
+    match (type_, length, precision, scale) {
+        (
+            "CHAR" | "NCHAR" | "VARCHAR" | "VARCHAR2" | "NVARCHAR" | "NVARCHAR2" | "RAW",
+            Some(length),
+            ..,
+        ) => format!("{type_}({length})"),
+        ("NUMBER", _, Some(precision), Some(scale)) if scale != "0" => {
+            format!("NUMBER({precision},{scale})")
+        }
+        ("NUMBER", _, Some(precision), _) => format!("NUMBER({precision})"),
+        _ => type_.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::wrapper::Error;
+
+    use super::{
+        columns, data_type, nullable_columns, split_name, Catalog, Constraint, ConstraintType,
+        Object, Source, Views,
+    };
+
+    /// Answers queries by the closure.
+    struct Fake<F>(F);
+
+    impl<F: Fn(&str, &[&str]) -> Result<Vec<Vec<Option<String>>>, Error>> Source for Fake<F> {
+        fn query(&self, sql: &str, parameters: &[&str]) -> Result<Vec<Vec<Option<String>>>, Error> {
+            (self.0)(sql, parameters)
+        }
+    }
+
+    fn row(values: &[Option<&str>]) -> Vec<Option<String>> {
+        values.iter().map(|x| x.map(str::to_string)).collect()
+    }
+
+    fn object(owner: &str, name: &str, type_: &str) -> Object {
+        Object {
+            owner: owner.to_string(),
+            name: name.to_string(),
+            type_: type_.to_string(),
+        }
+    }
+
+    #[test]
+    fn views() {
+        let readable = Fake(|_: &str, _: &[&str]| Ok(vec![]));
+        assert_eq!(Catalog::new(&readable).views_used(), Views::Dba);
+        let unreadable = Fake(|_: &str, _: &[&str]| Err(Error::Other));
+        assert_eq!(Catalog::new(&unreadable).views_used(), Views::All);
+    }
+
+    #[test]
+    fn find_object() {
+        let objects = [
+            ("APP", "ORDERS", "TABLE"),
+            ("PUBLIC", "ORDERS", "SYNONYM"),
+            ("PUBLIC", "DUAL", "SYNONYM"),
+            ("SALES", "ORDERS", "TABLE"),
+        ];
+        // the current schema is `APP`
+        let source = Fake(|sql: &str, parameters: &[&str]| {
+            let (owners, name) = match parameters {
+                [owner, name] => (vec![*owner], *name),
+                [name] if sql.contains("'PUBLIC'") => (vec!["APP", "PUBLIC"], *name),
+                _ => panic!("{sql}"),
+            };
+            let mut found = objects
+                .iter()
+                .filter(|x| owners.contains(&x.0) && x.1 == name)
+                .collect::<Vec<_>>();
+            // `order by decode(owner, 'PUBLIC', 1, 0)`
+            found.sort_by_key(|x| x.0 == "PUBLIC");
+            Ok(found
+                .into_iter()
+                .map(|x| row(&[Some(x.0), Some(x.1), Some(x.2)]))
+                .collect())
+        });
+        let catalog = Catalog::with_views(&source, Views::All);
+        assert_eq!(
+            catalog.find_object(None, "ORDERS").unwrap(),
+            Some(object("APP", "ORDERS", "TABLE"))
+        );
+        assert_eq!(
+            catalog.find_object(None, "DUAL").unwrap(),
+            Some(object("PUBLIC", "DUAL", "SYNONYM"))
+        );
+        assert_eq!(
+            catalog.find_object(Some("SALES"), "ORDERS").unwrap(),
+            Some(object("SALES", "ORDERS", "TABLE"))
+        );
+        assert_eq!(catalog.find_object(Some("SALES"), "DUAL").unwrap(), None);
+
+        let malformed = Fake(|_: &str, _: &[&str]| Ok(vec![row(&[Some("APP")])]));
+        let catalog = Catalog::with_views(&malformed, Views::All);
+        assert!(catalog.find_object(None, "ORDERS").is_err());
+    }
+
+    #[test]
+    fn constraints() {
+        let source = Fake(|_: &str, _: &[&str]| {
+            Ok(vec![
+                row(&[Some("PK_ORDERS"), Some("P"), None, None, Some("ID"), None]),
+                row(&[
+                    Some("FK_ITEM"),
+                    Some("R"),
+                    Some("APP"),
+                    Some("ITEMS"),
+                    Some("ITEM_NO"),
+                    Some("NO"),
+                ]),
+                row(&[
+                    Some("FK_ITEM"),
+                    Some("R"),
+                    Some("APP"),
+                    Some("ITEMS"),
+                    Some("ITEM_REV"),
+                    Some("REV"),
+                ]),
+                row(&[Some("CK_AMOUNT"), Some("C"), None, None, None, None]),
+            ])
+        });
+        let catalog = Catalog::with_views(&source, Views::All);
+        let strings = |x: &[&str]| x.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            catalog.constraints("APP", "ORDERS").unwrap(),
+            vec![
+                Constraint {
+                    name: "PK_ORDERS".to_string(),
+                    type_: ConstraintType::PrimaryKey,
+                    columns: strings(&["ID"]),
+                    references: None,
+                },
+                Constraint {
+                    name: "FK_ITEM".to_string(),
+                    type_: ConstraintType::ForeignKey,
+                    columns: strings(&["ITEM_NO", "ITEM_REV"]),
+                    references: Some(("APP.ITEMS".to_string(), strings(&["NO", "REV"]))),
+                },
+                Constraint {
+                    name: "CK_AMOUNT".to_string(),
+                    type_: ConstraintType::Check,
+                    columns: vec![],
+                    references: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn name() {
+        assert_eq!(split_name("orders"), (None, "ORDERS".to_string()));
+        assert_eq!(
+            split_name("sales.orders"),
+            (Some("SALES".to_string()), "ORDERS".to_string())
+        );
+        assert_eq!(
+            split_name(r#""Sales"."a.b""#),
+            (Some("Sales".to_string()), "a.b".to_string())
+        );
+    }
+
+    #[test]
+    fn rows() {
+        assert_eq!(
+            columns(vec![Some("A".to_string()), None]).unwrap(),
+            ["A".to_string(), String::new()]
+        );
+        assert!(columns::<3>(vec![Some("A".to_string())]).is_err());
+        assert!(nullable_columns::<1>(vec![None, None]).is_err());
+    }
+
+    #[test]
+    fn types() {
+        assert_eq!(data_type("VARCHAR", Some("20"), None, None), "VARCHAR(20)");
+        assert_eq!(
+            data_type("NUMBER", Some("22"), Some("10"), Some("2")),
+            "NUMBER(10,2)"
+        );
+        assert_eq!(
+            data_type("NUMBER", Some("22"), Some("10"), Some("0")),
+            "NUMBER(10)"
+        );
+        assert_eq!(data_type("NUMBER", Some("22"), None, None), "NUMBER");
+        assert_eq!(data_type("DATE", Some("7"), None, None), "DATE");
+    }
+}
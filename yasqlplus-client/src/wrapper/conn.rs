@@ -1,4 +1,4 @@
-use crate::native::{yacConnect, yacDisconnect, EnYacResult_YAC_ERROR};
+use crate::native::{yacCommit, yacConnect, yacDisconnect, yacRollback, EnYacResult_YAC_ERROR};
 
 use super::{DbcHandle, EnvHandle, Error, Statement, StatementHandle};
 
@@ -39,6 +39,22 @@ impl Connection {
         let stmt = StatementHandle::new(&self.conn_handle)?;
         Ok(stmt.into())
     }
+
+    pub fn commit(&self) -> Result<(), Error> {
+        if EnYacResult_YAC_ERROR == unsafe { yacCommit(self.conn_handle.0) } {
+            Err(Error::get_yas_diag(None).unwrap())
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn rollback(&self) -> Result<(), Error> {
+        if EnYacResult_YAC_ERROR == unsafe { yacRollback(self.conn_handle.0) } {
+            Err(Error::get_yas_diag(None).unwrap())
+        } else {
+            Ok(())
+        }
+    }
 }
 
 impl Drop for Connection {
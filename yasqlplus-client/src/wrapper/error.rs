@@ -9,10 +9,19 @@ pub enum Error {
     // Other(#[from] Box<dyn std::error::Error>),
     #[error("Unknown error")]
     Other,
+
+    /// Results not as the query should return.
+    #[error("Unexpected result: {0}")]
+    Unexpected(String),
 }
 
 impl Error {
     pub fn get_yas_diag(sql: Option<String>) -> Option<Error> {
         get_error(sql).map(Error::YasClient)
     }
+
+    /// Whether the connection is broken, by SQL state of class `08`.
+    pub fn is_connection_lost(&self) -> bool {
+        matches!(self, Error::YasClient(diag) if diag.sql_state.starts_with("08"))
+    }
 }
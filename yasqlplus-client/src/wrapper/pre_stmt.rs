@@ -1,5 +1,114 @@
-use super::StatementHandle;
+use std::ffi::CStr;
 
-pub struct PreparedStatement(pub(crate) StatementHandle);
+use crate::native::{
+    yacBindParameter, yacExecute, EnYacParamDirection_YAC_PARAM_INOUT,
+    EnYacParamDirection_YAC_PARAM_INPUT, EnYacParamDirection_YAC_PARAM_OUTPUT,
+    EnYacResult_YAC_ERROR,
+};
 
-impl PreparedStatement {}
+use super::{Error, LazyExecuted, StatementHandle, Type};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamDirection {
+    Input,
+    Output,
+    InputOutput,
+}
+
+/// A parameter bound as text, the database converts it to the target type.
+pub struct Parameter {
+    direction: ParamDirection,
+    type_: Type,
+    buffer: Vec<u8>,
+    indicator: i32,
+}
+
+impl Parameter {
+    /// `size` is the maximum length in bytes of the value.
+    pub fn new(direction: ParamDirection, type_: Type, size: usize, value: Option<&str>) -> Self {
+        let mut buffer = vec![0u8; size.max(value.map_or(0, str::len)) + 1];
+        let indicator = match value {
+            Some(value) => {
+                buffer[..value.len()].copy_from_slice(value.as_bytes());
+                value.len() as _
+            }
+            None => -1,
+        };
+        Self {
+            direction,
+            type_,
+            buffer,
+            indicator,
+        }
+    }
+
+    pub fn direction(&self) -> ParamDirection {
+        self.direction
+    }
+
+    pub fn value(&self) -> Option<String> {
+        if self.indicator < 0 {
+            None
+        } else {
+            let value = &self.buffer[..(self.indicator as usize).min(self.buffer.len())];
+            let value = CStr::from_bytes_until_nul(value)
+                .map(|x| x.to_bytes())
+                .unwrap_or(value);
+            Some(String::from_utf8_lossy(value).into_owned())
+        }
+    }
+}
+
+pub struct PreparedStatement {
+    handle: StatementHandle,
+    sql: String,
+    parameters: Vec<Parameter>,
+}
+
+impl PreparedStatement {
+    pub(crate) fn new(handle: StatementHandle, sql: &str) -> Self {
+        Self {
+            handle,
+            sql: sql.to_string(),
+            parameters: vec![],
+        }
+    }
+
+    /// Parameters are bound by position in the order they are added.
+    pub fn bind_parameter(&mut self, parameter: Parameter) {
+        self.parameters.push(parameter);
+    }
+
+    /// Executes the statement and gives back the parameters with output values filled in.
+    pub fn execute(mut self) -> Result<(LazyExecuted, Vec<Parameter>), Error> {
+        for (id, parameter) in self.parameters.iter_mut().enumerate() {
+            let direction = match parameter.direction {
+                ParamDirection::Input => EnYacParamDirection_YAC_PARAM_INPUT,
+                ParamDirection::Output => EnYacParamDirection_YAC_PARAM_OUTPUT,
+                ParamDirection::InputOutput => EnYacParamDirection_YAC_PARAM_INOUT,
+            };
+            if EnYacResult_YAC_ERROR
+                == unsafe {
+                    yacBindParameter(
+                        self.handle.0,
+                        (id + 1) as _, // parameter ids start from 1
+                        direction,
+                        parameter.type_ as _,
+                        parameter.buffer.as_mut_ptr() as _,
+                        parameter.buffer.len() as _,
+                        parameter.buffer.len() as _,
+                        &mut parameter.indicator as *mut _ as _,
+                    )
+                }
+            {
+                return Err(Error::get_yas_diag(Some(self.sql)).unwrap());
+            }
+        }
+
+        if EnYacResult_YAC_ERROR == unsafe { yacExecute(self.handle.0) } {
+            Err(Error::get_yas_diag(Some(self.sql)).unwrap())
+        } else {
+            Ok((LazyExecuted(self.handle), self.parameters))
+        }
+    }
+}
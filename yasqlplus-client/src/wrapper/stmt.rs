@@ -22,7 +22,7 @@ impl Statement {
         {
             Err(Error::get_yas_diag(Some(sql.to_string())).unwrap())
         } else {
-            Ok(PreparedStatement(self.0))
+            Ok(PreparedStatement::new(self.0, sql))
         }
     }
 }